@@ -0,0 +1,347 @@
+use std::collections::{BTreeMap, HashMap};
+
+use git2::Oid;
+
+use crate::{code::get_repowalk_data, error::Failure, traits::Repo};
+
+/// Authors need this many commits for their hour histogram to mean anything.
+/// Higher than [`crate::authors::MIN_AUTHOR_COMMITS`]: averages settle after
+/// a few commits, but 10 commits spread over 24 hours are already sparse.
+pub const MIN_HISTOGRAM_COMMITS: usize = 10;
+/// Window and commit count of an implausible burst of commits.
+pub const BURST_WINDOW_SECS: i64 = 3600;
+pub const BURST_COMMITS: usize = 40;
+/// A pause longer than this between two commits starts a new session.
+pub const SESSION_GAP_SECS: i64 = 2 * 3600;
+/// Sessions listed by `--explain`.
+const EVIDENCE_SESSIONS: usize = 5;
+
+/// Characters of the text histograms, from empty to the fullest bin.
+const RAMP: &[u8] = b" .:-=+*#%@";
+
+/// Author time of a commit. Author rather than committer time, so rebases
+/// don't squeeze history into a single moment.
+#[derive(Clone)]
+pub struct CommitTime {
+    pub id: Oid,
+    /// Key from [`crate::authors::author_key`].
+    pub author: String,
+    /// Unix seconds.
+    pub seconds: i64,
+    /// Offset of the author's timezone from UTC.
+    pub offset_minutes: i32,
+    /// Committed by a bot identity, which may well commit around the clock.
+    pub bot: bool,
+}
+
+impl CommitTime {
+    /// Time of `commit` by `author`, its mailmapped author signature.
+    pub fn of(commit: &git2::Commit, author: &git2::Signature) -> Self {
+        let when = author.when();
+        Self {
+            id: commit.id(),
+            author: crate::authors::author_key(author),
+            seconds: when.seconds(),
+            offset_minutes: when.offset_minutes(),
+            bot: crate::messages::is_bot(author),
+        }
+    }
+
+    /// Seconds since the epoch in the author's own timezone.
+    fn local(&self) -> i64 {
+        self.seconds + self.offset_minutes as i64 * 60
+    }
+
+    pub fn hour(&self) -> usize {
+        (self.local().rem_euclid(86400) / 3600) as usize
+    }
+
+    /// Day of the week, Monday is 0.
+    pub fn weekday(&self) -> usize {
+        // 1970-01-01 was a Thursday
+        (self.local().div_euclid(86400) + 3).rem_euclid(7) as usize
+    }
+}
+
+/// Hour-of-day and weekday distribution of one author's commits.
+pub struct AuthorHours {
+    pub author: String,
+    pub commits: usize,
+    pub hours: [usize; 24],
+    pub weekdays: [usize; 7],
+    /// Entropy of `hours` scaled to 0..1, where 1 is an even spread over the whole day.
+    pub entropy: f64,
+}
+
+impl AuthorHours {
+    fn new(author: &str, times: &[&CommitTime]) -> Self {
+        let mut hours = [0; 24];
+        let mut weekdays = [0; 7];
+        for t in times {
+            hours[t.hour()] += 1;
+            weekdays[t.weekday()] += 1;
+        }
+        Self {
+            author: author.to_string(),
+            commits: times.len(),
+            hours,
+            weekdays,
+            entropy: normalized_entropy(&hours),
+        }
+    }
+
+    /// Text histograms for `--explain`.
+    pub fn render(&self) -> Vec<String> {
+        vec![
+            format!("{} ({} commits, entropy {:.2})", self.author, self.commits, self.entropy),
+            format!("  hours 00-23 [{}]", histogram(&self.hours)),
+            format!("  days Mon-Sun [{}]", histogram(&self.weekdays)),
+        ]
+    }
+}
+
+/// Most commits by one author within [`BURST_WINDOW_SECS`].
+pub struct Burst {
+    pub author: String,
+    pub commits: usize,
+    /// Start of the window, unix seconds.
+    pub start: i64,
+}
+
+pub struct CommitHours {
+    /// Authors with at least [`MIN_HISTOGRAM_COMMITS`] commits, most active first.
+    pub authors: Vec<AuthorHours>,
+    pub burst: Option<Burst>,
+}
+
+pub fn get_commit_hours(repo: &Repo) -> Result<CommitHours, Failure> {
+    commit_hours(&get_repowalk_data(repo)?.times)
+}
+
+/// Histograms and largest burst of human authors, bots are left out.
+fn commit_hours(times: &[CommitTime]) -> Result<CommitHours, Failure> {
+    let mut by_author: BTreeMap<&str, Vec<&CommitTime>> = BTreeMap::new();
+    for t in times.iter().filter(|t| !t.bot) {
+        by_author.entry(t.author.as_str()).or_default().push(t);
+    }
+    let mut authors: Vec<AuthorHours> = by_author
+        .iter()
+        .filter(|(_, times)| times.len() >= MIN_HISTOGRAM_COMMITS)
+        .map(|(author, times)| AuthorHours::new(author, times))
+        .collect();
+    if authors.is_empty() {
+        return Err(Failure::missing(format!(
+            "no author with at least {} commits for an hour histogram",
+            MIN_HISTOGRAM_COMMITS
+        )));
+    }
+    authors.sort_by_key(|a| std::cmp::Reverse(a.commits));
+    let burst = by_author
+        .iter()
+        .map(|(author, times)| {
+            let (commits, start) = largest_burst(times);
+            Burst { author: author.to_string(), commits, start }
+        })
+        .max_by_key(|b| b.commits);
+    Ok(CommitHours { authors, burst })
+}
+
+/// Largest number of commits within [`BURST_WINDOW_SECS`], with the window start.
+fn largest_burst(times: &[&CommitTime]) -> (usize, i64) {
+    let mut seconds: Vec<i64> = times.iter().map(|t| t.seconds).collect();
+    seconds.sort_unstable();
+    let mut best = (0, 0);
+    let mut first = 0;
+    for (last, &s) in seconds.iter().enumerate() {
+        while s - seconds[first] >= BURST_WINDOW_SECS {
+            first += 1;
+        }
+        if last - first + 1 > best.0 {
+            best = (last - first + 1, seconds[first]);
+        }
+    }
+    best
+}
+
+/// Commits without a pause longer than [`SESSION_GAP_SECS`].
+pub struct Session {
+    /// Unix seconds of the first and the last commit.
+    pub start: i64,
+    pub end: i64,
+    pub commits: usize,
+    /// Lines inserted by the session's commits.
+    pub lines: usize,
+}
+
+impl Session {
+    pub fn hours(&self) -> f64 {
+        (self.end - self.start) as f64 / 3600.0
+    }
+}
+
+pub struct Sessions {
+    /// In chronological order.
+    pub sessions: Vec<Session>,
+    pub median_hours: f64,
+    pub lines_per_session: f64,
+    /// Share of all inserted lines that landed in the largest session.
+    pub largest_share: f64,
+}
+
+impl Sessions {
+    pub fn largest(&self) -> &Session {
+        self.sessions.iter().max_by_key(|s| s.lines).expect("at least one session")
+    }
+
+    /// Sessions with the most lines for `--explain`.
+    pub fn render(&self) -> Vec<String> {
+        let mut sessions: Vec<&Session> = self.sessions.iter().collect();
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.lines));
+        sessions
+            .iter()
+            .take(EVIDENCE_SESSIONS)
+            .map(|s| format!("{}, {:.1} h, {} commits, +{} lines", format_utc(s.start), s.hours(), s.commits, s.lines))
+            .collect()
+    }
+}
+
+pub fn get_sessions(repo: &Repo) -> Result<Sessions, Failure> {
+    let data = get_repowalk_data(repo)?;
+    let lines: HashMap<Oid, usize> = data.commits.iter().map(|c| (c.id, c.insertions)).collect();
+    let mut times: Vec<(i64, usize)> = data
+        .times
+        .iter()
+        .map(|t| (t.seconds, lines.get(&t.id).copied().unwrap_or(0)))
+        .collect();
+    times.sort_unstable();
+    let sessions = sessionize(&times);
+    let total: usize = sessions.iter().map(|s| s.lines).sum();
+    if total == 0 {
+        return Err(Failure::missing("no inserted lines to split into sessions"));
+    }
+    let mut hours: Vec<f64> = sessions.iter().map(Session::hours).collect();
+    hours.sort_by(f64::total_cmp);
+    let median_hours = match hours.len() % 2 {
+        1 => hours[hours.len() / 2],
+        _ => (hours[hours.len() / 2 - 1] + hours[hours.len() / 2]) / 2.0,
+    };
+    let largest = sessions.iter().map(|s| s.lines).max().unwrap_or(0);
+    Ok(Sessions {
+        median_hours,
+        lines_per_session: total as f64 / sessions.len() as f64,
+        largest_share: largest as f64 / total as f64,
+        sessions,
+    })
+}
+
+/// Groups `(seconds, lines)` of commits, sorted by time, into sessions.
+fn sessionize(times: &[(i64, usize)]) -> Vec<Session> {
+    let mut sessions: Vec<Session> = Vec::new();
+    for &(seconds, lines) in times {
+        match sessions.last_mut() {
+            Some(s) if seconds - s.end <= SESSION_GAP_SECS => {
+                s.end = seconds;
+                s.commits += 1;
+                s.lines += lines;
+            }
+            _ => sessions.push(Session { start: seconds, end: seconds, commits: 1, lines }),
+        }
+    }
+    sessions
+}
+
+/// `YYYY-MM-DD HH:MM UTC` for evidence output.
+pub fn format_utc(seconds: i64) -> String {
+    let days = seconds.div_euclid(86400);
+    let secs = seconds.rem_euclid(86400);
+    // Civil-from-days, Howard Hinnant's algorithm
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02} {:02}:{:02} UTC", year, month, day, secs / 3600, secs % 3600 / 60)
+}
+
+/// Shannon entropy of `bins` divided by the maximum for that many bins.
+fn normalized_entropy(bins: &[usize]) -> f64 {
+    let total: usize = bins.iter().sum();
+    if total == 0 || bins.len() < 2 {
+        return 0.0;
+    }
+    let entropy: f64 = bins
+        .iter()
+        .filter(|&&n| n > 0)
+        .map(|&n| {
+            let p = n as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum();
+    entropy / (bins.len() as f64).log2()
+}
+
+fn histogram(bins: &[usize]) -> String {
+    let max = bins.iter().copied().max().unwrap_or(0).max(1);
+    bins.iter()
+        .map(|&n| {
+            let level = (n * (RAMP.len() - 1)).div_ceil(max);
+            RAMP[level] as char
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn at(seconds: i64, offset_minutes: i32) -> CommitTime {
+        CommitTime { id: Oid::zero(), author: String::from("a"), seconds, offset_minutes, bot: false }
+    }
+
+    #[test]
+    fn test_local_hour_and_weekday() {
+        // 2024-01-01 (a Monday) 23:30 UTC is Tuesday 01:30 at UTC+2
+        let t = at(1704151800, 120);
+        assert_eq!((t.hour(), t.weekday()), (1, 1));
+        assert_eq!((at(1704151800, 0).hour(), at(1704151800, 0).weekday()), (23, 0));
+    }
+
+    #[test]
+    fn test_entropy_and_burst() {
+        assert_eq!(normalized_entropy(&[5; 24]), 1.0);
+        assert_eq!(normalized_entropy(&[0, 10, 0, 0]), 0.0);
+        let times: Vec<CommitTime> = (0..50).map(|i| at(i * 60, 0)).chain([at(100_000, 0)]).collect();
+        let refs: Vec<&CommitTime> = times.iter().collect();
+        assert_eq!(largest_burst(&refs), (50, 0));
+        assert_eq!(histogram(&[0, 1, 9]), " .@");
+        assert_eq!(format_utc(1704151800), "2024-01-01 23:30 UTC");
+    }
+
+    #[test]
+    fn test_bots_are_left_out() {
+        let day = 24 * 3600;
+        let human = (0..10).map(|i| at(i * day + 10 * 3600, 0));
+        // Round the clock, and 48 commits within the first hour
+        let bot = (0..96).map(|i| match i < 48 {
+            true => CommitTime { author: String::from("dependabot[bot]"), bot: true, ..at(i * 60, 0) },
+            false => CommitTime { author: String::from("dependabot[bot]"), bot: true, ..at(i * 3600, 0) },
+        });
+        let times: Vec<CommitTime> = human.chain(bot).collect();
+        let hours = commit_hours(&times).unwrap();
+        assert_eq!(hours.authors.len(), 1);
+        assert_eq!(hours.authors[0].entropy, 0.0);
+        assert_eq!(hours.burst.map(|b| b.commits), Some(1));
+    }
+
+    #[test]
+    fn test_sessionize() {
+        let hour = 3600;
+        let sessions = sessionize(&[(0, 10), (hour, 20), (3 * hour, 5), (6 * hour, 100), (7 * hour + 1800, 1)]);
+        let found: Vec<(f64, usize, usize)> = sessions.iter().map(|s| (s.hours(), s.commits, s.lines)).collect();
+        assert_eq!(found, vec![(3.0, 3, 35), (1.5, 2, 101)]);
+    }
+}
@@ -0,0 +1,279 @@
+use std::{collections::HashSet, ops::Deref, path::PathBuf, sync::Arc};
+
+use git2::Oid;
+use once_cell::sync::OnceCell;
+
+use crate::{code::WalkerResult, error::{AnalysisError, Failure}, options::Options, repo::{last_fetched, open_repository, resolve_ref, Target}};
+
+/// Repository state an analysis was computed for.
+///
+/// `head` is the commit analysis starts from: HEAD, the commit `--ref`
+/// resolved to, or the tip of `--range`. `base` is the excluded side of
+/// `--range`.
+#[derive(Clone, Debug)]
+pub struct RepoState {
+    pub path: PathBuf,
+    pub head: Option<Oid>,
+    pub base: Option<Oid>,
+}
+
+/// Per-repository state shared by all rules of a single run.
+///
+/// `git2::Repository` is not `Sync`, so every rule opens its own handle
+/// through [`Analysis::open`], while expensive results (like the revwalk)
+/// are computed once here and reused.
+pub struct Analysis {
+    pub target: Target,
+    pub state: RepoState,
+    pub options: Options,
+    /// Boundary commits of a shallow clone, history beyond them is missing.
+    pub shallow_roots: HashSet<Oid>,
+    /// Last fetch of a cached clone (unix seconds), `None` for local targets.
+    pub fetched: Option<i64>,
+    pub(crate) walk: OnceCell<Result<WalkerResult, Failure>>,
+    pub(crate) touched: OnceCell<Option<HashSet<String>>>,
+}
+
+impl Analysis {
+    pub fn new(target: &Target, options: &Options) -> Result<Arc<Self>, AnalysisError> {
+        let repo = open_repository(target, options)?;
+        let (base, head) = match (options.range_ends(), &options.git_ref) {
+            (Some((base, tip)), _) => (Some(resolve_ref(&repo, base)?), Some(resolve_ref(&repo, tip)?)),
+            (None, Some(r)) => (None, Some(resolve_ref(&repo, r)?)),
+            (None, None) => (None, repo.head().ok()
+                .and_then(|h| h.peel_to_commit().ok())
+                .map(|c| c.id())),
+        };
+        if head.is_none() {
+            return Err(AnalysisError::EmptyRepository);
+        }
+        let shallow_roots = read_shallow_roots(&repo);
+        Ok(Arc::new(Self {
+            target: target.clone(),
+            state: RepoState {
+                path: repo.path().to_path_buf(),
+                head,
+                base,
+            },
+            options: options.clone(),
+            shallow_roots,
+            fetched: match target {
+                Target::Remote(_) => last_fetched(&repo),
+                Target::Local(_) => None,
+            },
+            walk: OnceCell::new(),
+            touched: OnceCell::new(),
+        }))
+    }
+
+    pub fn open(self: &Arc<Self>) -> Result<Repo, AnalysisError> {
+        let git = git2::Repository::open(&self.state.path)
+            .map_err(|e| AnalysisError::read(format!("repository at {}", self.state.path.display()), e))?;
+        Ok(Repo {
+            git,
+            analysis: Arc::clone(self),
+        })
+    }
+}
+
+/// Commits listed in `.git/shallow` (the common dir for linked worktrees),
+/// empty for complete clones.
+fn read_shallow_roots(repo: &git2::Repository) -> HashSet<Oid> {
+    if !repo.is_shallow() {
+        return HashSet::new();
+    }
+    std::fs::read_to_string(repo.commondir().join("shallow"))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|l| Oid::from_str(l.trim()).ok())
+        .collect()
+}
+
+/// Repository handle given to rules, tied to the shared [`Analysis`].
+pub struct Repo {
+    git: git2::Repository,
+    analysis: Arc<Analysis>,
+}
+
+impl Repo {
+    pub fn analysis(&self) -> &Analysis {
+        &self.analysis
+    }
+
+    /// Commit the analysis starts from, HEAD unless `--ref` was given.
+    pub fn start_commit(&self) -> Option<git2::Commit<'_>> {
+        self.git.find_commit(self.analysis.state.head?).ok()
+    }
+
+    pub fn start_tree(&self) -> Option<git2::Tree<'_>> {
+        self.start_commit()?.tree().ok()
+    }
+
+    pub fn is_shallow_root(&self, oid: Oid) -> bool {
+        self.analysis.shallow_roots.contains(&oid)
+    }
+
+    /// What the analysis starts from as given by the user, for messages.
+    pub fn start_label(&self) -> &str {
+        let options = &self.analysis.options;
+        options.range.as_deref().or(options.git_ref.as_deref()).unwrap_or("HEAD")
+    }
+
+    /// Cause for a walk from [`Repo::start_commit`] that found no commits.
+    pub fn no_commits(&self) -> Failure {
+        match self.is_range() {
+            true => Failure::missing(format!("no commits in range {}", self.start_label())),
+            false => Failure::missing(format!("no commits reachable from {}", self.start_label())),
+        }
+    }
+
+    /// Whether analysis is restricted to a `--range`.
+    pub fn is_range(&self) -> bool {
+        self.analysis.state.base.is_some()
+    }
+
+    /// Revwalk starting at [`Repo::start_commit`], limited to `--range` if given.
+    pub fn walk_from_start(&self) -> Option<git2::Revwalk<'_>> {
+        let mut walker = self.walk_history()?;
+        if let Some(base) = self.analysis.state.base {
+            walker.hide(base).ok()?;
+        }
+        Some(walker)
+    }
+
+    /// Revwalk over the whole history reachable from [`Repo::start_commit`].
+    pub fn walk_history(&self) -> Option<git2::Revwalk<'_>> {
+        let mut walker = self.git.revwalk().ok()?;
+        walker.push(self.analysis.state.head?).ok()?;
+        Some(walker)
+    }
+}
+
+impl Deref for Repo {
+    type Target = git2::Repository;
+
+    fn deref(&self) -> &Self::Target {
+        &self.git
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::*;
+    use crate::{code::get_repowalk_data, staging_dir::StagingDir};
+
+    /// Writes `files` into `dir` and commits them on top of HEAD.
+    fn commit_files(git: &git2::Repository, files: &[(&str, String)]) -> Oid {
+        let dir = git.workdir().unwrap();
+        let mut index = git.index().unwrap();
+        for (name, content) in files {
+            std::fs::write(dir.join(name), content).unwrap();
+            index.add_path(Path::new(name)).unwrap();
+        }
+        index.write().unwrap();
+        let tree = git.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let parent = git.head().ok().and_then(|h| h.peel_to_commit().ok());
+        git.commit(Some("HEAD"), &sig, &sig, "change", &tree, parent.iter().collect::<Vec<_>>().as_slice())
+            .unwrap()
+    }
+
+    fn init_repo(dir: &Path, file: &str) {
+        let repo = git2::Repository::init(dir).unwrap();
+        std::fs::write(dir.join(file), "content\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(file)).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[]).unwrap();
+    }
+
+    #[test]
+    fn test_walk_data_is_per_repository() {
+        let root = std::env::temp_dir().join(format!("vibe-coded-test-{}", std::process::id()));
+        let first = StagingDir::try_new(&root.join("first")).unwrap();
+        let second = StagingDir::try_new(&root.join("second")).unwrap();
+        init_repo(&first.path, "alpha");
+        init_repo(&second.path, "beta");
+
+        for (dir, file) in [(&first, "alpha"), (&second, "beta")] {
+            let analysis = Analysis::new(&Target::Local(dir.path.clone()), &Options::default()).unwrap();
+            let repo = analysis.open().unwrap();
+            let files = &get_repowalk_data(&repo).unwrap().files;
+            assert_eq!(files, &vec![String::from(file)]);
+        }
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn test_root_commit_is_diffed_against_empty_tree() {
+        let root = std::env::temp_dir().join(format!("vibe-coded-test-root-{}", std::process::id()));
+        let dir = StagingDir::try_new(&root).unwrap();
+        let git = git2::Repository::init(&dir.path).unwrap();
+        let initial = commit_files(&git, &[("big.rs", "x\n".repeat(2000))]);
+        for i in 0..6 {
+            commit_files(&git, &[(&format!("small{}.rs", i), "a\nb\nc\n".into())]);
+        }
+        let analysis = Analysis::new(&Target::Local(dir.path.clone()), &Options::default()).unwrap();
+        let repo = analysis.open().unwrap();
+        let data = get_repowalk_data(&repo).unwrap();
+        assert_eq!(data.commits.len(), 7);
+        let stat = data.commits.iter().find(|c| c.id == initial).unwrap();
+        assert_eq!((stat.insertions, stat.deletions), (2000, 0));
+        assert!(crate::code::largest_insertions(&repo)[0].contains("+2000"));
+        assert_eq!(data.authors[0].commits, 7);
+
+        let single = StagingDir::try_new(&root.join("single")).unwrap();
+        init_repo(&single.path, "alpha");
+        let analysis = Analysis::new(&Target::Local(single.path.clone()), &Options::default()).unwrap();
+        let repo = analysis.open().unwrap();
+        let commits = &get_repowalk_data(&repo).unwrap().commits;
+        assert_eq!(commits.iter().map(|c| c.insertions).collect::<Vec<_>>(), [1]);
+    }
+
+    #[test]
+    fn test_short_history_reports_cause() {
+        let root = std::env::temp_dir().join(format!("vibe-coded-test-short-{}", std::process::id()));
+        let dir = StagingDir::try_new(&root).unwrap();
+        init_repo(&dir.path, "alpha");
+        let analysis = Analysis::new(&Target::Local(dir.path.clone()), &Options::default()).unwrap();
+        let repo = analysis.open().unwrap();
+        let failure = crate::code::average_code_insertions_per_commit(&repo).unwrap_err();
+        assert_eq!(failure.to_string(), "fewer than 4 commit diffs for mean_iqr, found 1");
+        let failure = crate::readme::get_readme(&repo).err().unwrap();
+        assert_eq!(failure.to_string(), "no README in tree");
+    }
+
+    #[test]
+    fn test_empty_repository_is_an_error() {
+        let root = std::env::temp_dir().join(format!("vibe-coded-test-empty-{}", std::process::id()));
+        let dir = StagingDir::try_new(&root).unwrap();
+        git2::Repository::init(&dir.path).unwrap();
+        let error = Analysis::new(&Target::Local(dir.path.clone()), &Options::default()).err().unwrap();
+        assert!(matches!(error, AnalysisError::EmptyRepository));
+    }
+
+    #[test]
+    fn test_range_limits_touched_paths() {
+        let root = std::env::temp_dir().join(format!("vibe-coded-test-range-{}", std::process::id()));
+        let dir = StagingDir::try_new(&root).unwrap();
+        let git = git2::Repository::init(&dir.path).unwrap();
+        let base = commit_files(&git, &[("a.rs", "// a\n".into()), ("b.rs", "// b\n".into())]);
+        commit_files(&git, &[("b.rs", "// b\nfn b() {}\n".into())]);
+        let tip = commit_files(&git, &[("c.rs", "fn c() {}\n".into())]);
+
+        let whole = Analysis::new(&Target::Local(dir.path.clone()), &Options::default()).unwrap();
+        assert!(crate::code::touched_paths(&whole.open().unwrap()).is_none());
+
+        let options = Options { range: Some(format!("{}..{}", base, tip)), ..Default::default() };
+        let analysis = Analysis::new(&Target::Local(dir.path.clone()), &options).unwrap();
+        let repo = analysis.open().unwrap();
+        let mut touched: Vec<&String> = crate::code::touched_paths(&repo).unwrap().iter().collect();
+        touched.sort();
+        assert_eq!(touched, ["b.rs", "c.rs"]);
+        // a.rs is left out, so only b.rs (1 of 2 lines) and c.rs (0 of 1) count.
+        assert_eq!(crate::code::count_comment_ratio(&repo).unwrap(), (1, 3));
+    }
+}
@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{code::{analyze_msg_similarity, mean_iqr}, error::Failure, traits::Repo};
+
+/// Authors with fewer commits are left out of the per-author rule. Five
+/// commits give the four gaps `mean_iqr` needs; the hour histogram asks
+/// for more, see `activity::MIN_HISTOGRAM_COMMITS`.
+pub const MIN_AUTHOR_COMMITS: usize = 5;
+
+/// Canonical author of `commit` after applying the repository's `.mailmap`.
+pub fn author_of(commit: &git2::Commit, mailmap: Option<&git2::Mailmap>) -> git2::Signature<'static> {
+    mailmap
+        .and_then(|m| commit.author_with_mailmap(m).ok())
+        .unwrap_or_else(|| commit.author().to_owned())
+}
+
+/// Lowercased email of a (mailmapped) signature, the name when there is no
+/// email. Every per-author statistic groups commits by this key.
+pub fn author_key(author: &git2::Signature) -> String {
+    match author.email() {
+        Some(email) if !email.is_empty() => email.to_lowercase(),
+        _ => author.name().unwrap_or_default().to_lowercase(),
+    }
+}
+
+/// Raw numbers of one author, gathered during the revwalk.
+#[derive(Default)]
+pub(crate) struct AuthorWalk {
+    /// Name on the author's newest commit.
+    pub name: String,
+    /// Diff sizes of the commits paired with their predecessor in the walk.
+    pub insertions: Vec<usize>,
+    pub deletions: Vec<usize>,
+    /// Author time of every commit.
+    pub seconds: Vec<i64>,
+    pub messages: Vec<String>,
+}
+
+/// Commit statistics of a single author, same measures as the history-wide
+/// `code-*` and `message-similarity` rules. `None` where the author has too
+/// little history for a value.
+#[derive(Clone, Debug, Serialize)]
+pub struct AuthorStats {
+    /// Key from [`author_key`].
+    pub author: String,
+    pub name: String,
+    pub commits: usize,
+    pub insertions: Option<f64>,
+    pub deletions: Option<f64>,
+    /// Hours between the author's own consecutive commits.
+    pub gap_hours: Option<f64>,
+    pub msg_similarity: Option<f64>,
+    pub lines_per_hour: Option<f64>,
+}
+
+impl AuthorStats {
+    /// `Name <email>` for output.
+    pub fn label(&self) -> String {
+        match self.name.is_empty() || self.name.to_lowercase() == self.author {
+            true => self.author.clone(),
+            false => format!("{} <{}>", self.name, self.author),
+        }
+    }
+
+    pub(crate) fn from_walk(author: String, mut walk: AuthorWalk) -> Self {
+        walk.seconds.sort_unstable();
+        let mut gaps: Vec<usize> = walk.seconds.windows(2).map(|w| (w[1] - w[0]) as usize).collect();
+        let insertions = mean_iqr(&mut walk.insertions);
+        let gap_hours = mean_iqr(&mut gaps).map(|g| g / 3600.0);
+        let lines_per_hour = match (insertions, gap_hours) {
+            (Some(ins), Some(hours)) if hours > 0.0 => Some(ins / hours),
+            _ => None,
+        };
+        Self {
+            author,
+            name: walk.name,
+            commits: walk.seconds.len(),
+            insertions,
+            deletions: mean_iqr(&mut walk.deletions),
+            gap_hours,
+            msg_similarity: analyze_msg_similarity(&walk.messages),
+            lines_per_hour,
+        }
+    }
+}
+
+/// Per-author statistics, most active authors first.
+pub(crate) fn collect(walks: HashMap<String, AuthorWalk>) -> Vec<AuthorStats> {
+    let mut authors: Vec<AuthorStats> = walks.into_iter().map(|(author, walk)| AuthorStats::from_walk(author, walk)).collect();
+    authors.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.author.cmp(&b.author)));
+    authors
+}
+
+pub fn get_authors(repo: &Repo) -> Result<&[AuthorStats], Failure> {
+    Ok(&crate::code::get_repowalk_data(repo)?.authors)
+}
+
+/// Text table of `authors`, header first.
+pub fn table(authors: &[AuthorStats]) -> Vec<String> {
+    let cell = |v: Option<f64>| v.map_or(String::from("-"), |v| format!("{:.2}", v));
+    let width = authors.iter().map(|a| a.label().chars().count()).max().unwrap_or(0).max(6);
+    let mut lines = vec![format!(
+        "{:width$} {:>7} {:>9} {:>9} {:>8} {:>7} {:>8}",
+        "author", "commits", "ins/cmt", "del/cmt", "gap h", "msg sim", "lines/h"
+    )];
+    lines.extend(authors.iter().map(|a| {
+        format!(
+            "{:width$} {:>7} {:>9} {:>9} {:>8} {:>7} {:>8}",
+            a.label(),
+            a.commits,
+            cell(a.insertions),
+            cell(a.deletions),
+            cell(a.gap_hours),
+            cell(a.msg_similarity),
+            cell(a.lines_per_hour)
+        )
+    }));
+    lines
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_author_stats_from_walk() {
+        let walk = AuthorWalk {
+            name: String::from("A"),
+            insertions: vec![100, 120, 80, 100],
+            deletions: vec![0, 10, 0, 10],
+            seconds: vec![4 * 3600, 0, 3 * 3600, 3600, 2 * 3600],
+            messages: Vec::new(),
+        };
+        let stats = AuthorStats::from_walk(String::from("a@example.com"), walk);
+        assert_eq!(stats.commits, 5);
+        assert_eq!(stats.insertions, Some(100.0));
+        assert_eq!(stats.gap_hours, Some(1.0));
+        assert_eq!(stats.lines_per_hour, Some(100.0));
+        assert_eq!(stats.msg_similarity, None);
+    }
+}
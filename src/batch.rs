@@ -0,0 +1,156 @@
+use std::{cmp::Reverse, io::BufRead};
+
+use colored::Colorize;
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::{options::Options, repo::Target, report::{Format, JsonReport, Report}, run_rules};
+
+/// Outcome of a single repository in a batch run.
+pub struct BatchEntry {
+    pub target: Target,
+    pub result: Result<Report, anyhow::Error>,
+}
+
+pub struct BatchReport {
+    pub entries: Vec<BatchEntry>,
+}
+
+#[derive(Serialize)]
+struct JsonEntry<'a> {
+    target: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    report: Option<JsonReport<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Reads one URL or path per line, skipping blank lines and `#` comments.
+pub fn read_targets(input: impl BufRead) -> Result<Vec<Target>, anyhow::Error> {
+    let mut targets = Vec::new();
+    for line in input.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        targets.push(Target::parse(line));
+    }
+    Ok(targets)
+}
+
+/// Runs every rule against every target, at most `jobs` repositories at a time.
+///
+/// Rules of a repository run on the same bounded pool, so no more than `jobs`
+/// clones are in flight at once.
+pub fn run_batch(targets: &[Target], options: &Options, jobs: usize) -> Result<BatchReport, anyhow::Error> {
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs.max(1)).build()?;
+    let mut entries: Vec<BatchEntry> = pool.install(|| {
+        targets
+            .par_iter()
+            .map(|target| BatchEntry {
+                target: target.clone(),
+                result: run_rules(target, options),
+            })
+            .collect()
+    });
+    // Highest score first, repositories without a verdict and errors last
+    entries.sort_by_key(|e| match &e.result {
+        Ok(report) if report.has_results() => (0, Reverse(report.score())),
+        _ => (1, Reverse(None)),
+    });
+    Ok(BatchReport { entries })
+}
+
+impl BatchReport {
+    pub fn exceeds(&self, options: &Options) -> bool {
+        self.entries
+            .iter()
+            .any(|e| e.result.as_ref().is_ok_and(|r| r.exceeds(options)))
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.entries.iter().any(|e| e.result.as_ref().is_ok_and(|r| !r.has_results()) || e.result.is_err())
+    }
+
+    pub fn render(&self) {
+        println!("{:>4}  {:>6}  target", "rank", "score");
+        for (rank, entry) in self.entries.iter().enumerate() {
+            match &entry.result {
+                Ok(report) if !report.has_results() => {
+                    let reason = report.failed().first().map(|f| f.reason.clone()).unwrap_or_default();
+                    println!("{:>4}  {:>6}  {} ({})", "-", "FAIL".bright_red(), entry.target, reason);
+                }
+                Ok(report) => {
+                    let score = match report.score() {
+                        Some(s) => format!("{}%", s),
+                        None => String::from("-"),
+                    };
+                    println!("{:>4}  {:>6}  {}", rank + 1, score, entry.target);
+                }
+                Err(e) => {
+                    println!("{:>4}  {:>6}  {} ({:#})", "-", "FAIL".bright_red(), entry.target, e);
+                }
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, anyhow::Error> {
+        let entries: Vec<JsonEntry> = self
+            .entries
+            .iter()
+            .map(|e| JsonEntry {
+                target: e.target.to_string(),
+                report: e.result.as_ref().ok().map(Report::json),
+                error: e.result.as_ref().err().map(|e| format!("{:#}", e)),
+            })
+            .collect();
+        Ok(serde_json::to_string_pretty(&entries)?)
+    }
+
+    pub fn print(&self, format: Format) -> Result<(), anyhow::Error> {
+        match format {
+            Format::Text => self.render(),
+            Format::Json => println!("{}", self.to_json()?),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_read_targets_skips_blank_lines_and_comments() {
+        let input = "# candidates\nhttps://github.com/a/b\n\n   \n  # indented comment\n  git@github.com:c/d.git  \n.\n";
+        let targets: Vec<String> = read_targets(input.as_bytes()).unwrap().iter().map(|t| t.to_string()).collect();
+        assert_eq!(targets, vec!["https://github.com/a/b", "git@github.com:c/d.git", "."]);
+        assert!(matches!(read_targets(".\n".as_bytes()).unwrap()[0], Target::Local(_)));
+        assert!(matches!(read_targets("https://github.com/a/b\n".as_bytes()).unwrap()[0], Target::Remote(_)));
+        assert!(read_targets("# nothing\n\n".as_bytes()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_unusable_targets_fail_with_a_reason() {
+        let root = std::env::temp_dir().join(format!("vibe-coded-test-batch-{}", std::process::id()));
+        let dir = crate::staging_dir::StagingDir::try_new(&root).unwrap();
+        let missing = format!("file://{}", dir.path.join("gone").display());
+        let targets = [Target::Remote(missing), Target::Local(dir.path.clone())];
+        let options = Options { cache_dir: Some(dir.path.join("cache")), full_history: true, ..Default::default() };
+        let batch = run_batch(&targets, &options, 2).unwrap();
+        assert!(batch.has_errors());
+        let reasons: Vec<String> = batch
+            .entries
+            .iter()
+            .map(|e| {
+                let report = e.result.as_ref().unwrap();
+                assert!(!report.has_results());
+                report.failed()[0].reason.clone()
+            })
+            .collect();
+        assert!(reasons.iter().any(|r| r.starts_with("can't clone file://")), "{:?}", reasons);
+        assert!(reasons.iter().any(|r| r.starts_with("can't read repository at")), "{:?}", reasons);
+        assert!(batch.to_json().unwrap().contains("can't clone file://"));
+    }
+}
@@ -0,0 +1,189 @@
+use std::{path::{Path, PathBuf}, time::Duration};
+
+use anyhow::Context;
+use serde::Serialize;
+
+use crate::repo::{last_fetched, now, original_url};
+
+/// A cached clone under the cache root.
+#[derive(Serialize, Debug)]
+pub struct CacheEntry {
+    pub slug: String,
+    pub path: PathBuf,
+    /// URL the clone was made from.
+    pub url: String,
+    /// Size on disk in bytes.
+    pub size: u64,
+    /// Last fetch (unix seconds), `None` when not recorded.
+    pub fetched: Option<i64>,
+}
+
+impl CacheEntry {
+    /// `None` unless `path` is a clone made by vibe-coded, so a cache root
+    /// pointing somewhere else never lists (or removes) unrelated directories.
+    /// With `legacy`, clones from versions that didn't record their URL are
+    /// recognised by their `origin` remote, and aged by their last fetch on
+    /// disk.
+    fn read(path: &Path, legacy: bool) -> Option<Self> {
+        let repo = git2::Repository::open(path).ok()?;
+        let (url, fetched) = match original_url(&repo) {
+            Some(url) => (url, last_fetched(&repo)),
+            None if legacy => {
+                let url = repo.find_remote("origin").ok()?.url()?.to_string();
+                (url, modified(&repo.path().join("FETCH_HEAD")).or_else(|| modified(&repo.path().join("HEAD"))))
+            }
+            None => return None,
+        };
+        Some(Self {
+            slug: path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default(),
+            path: path.to_path_buf(),
+            url,
+            size: dir_size(path),
+            fetched,
+        })
+    }
+
+    /// Seconds since the last fetch.
+    pub fn age(&self) -> Option<i64> {
+        self.fetched.map(|f| (now() - f).max(0))
+    }
+}
+
+/// Clones made by vibe-coded under `root`, other directories are ignored.
+/// `legacy` also takes clones of older versions, only safe in the default
+/// root which nothing else writes to.
+pub fn list(root: &Path, legacy: bool) -> Result<Vec<CacheEntry>, anyhow::Error> {
+    if !root.exists() {
+        return Ok(Vec::new());
+    }
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(root).with_context(|| format!("Can't read cache {}", root.display()))? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            entries.extend(CacheEntry::read(&entry.path(), legacy));
+        }
+    }
+    entries.sort_by(|a, b| a.slug.cmp(&b.slug));
+    Ok(entries)
+}
+
+/// Removes clones not fetched for longer than `older_than`, returns what was
+/// removed. Clones of unknown age are kept.
+pub fn prune(root: &Path, legacy: bool, older_than: Duration) -> Result<Vec<CacheEntry>, anyhow::Error> {
+    let limit = older_than.as_secs() as i64;
+    let mut removed = Vec::new();
+    for entry in list(root, legacy)? {
+        if entry.age().is_some_and(|age| age > limit) {
+            std::fs::remove_dir_all(&entry.path)
+                .with_context(|| format!("Can't remove {}", entry.path.display()))?;
+            removed.push(entry);
+        }
+    }
+    Ok(removed)
+}
+
+/// Removes every cached clone, returns what was removed.
+pub fn clear(root: &Path, legacy: bool) -> Result<Vec<CacheEntry>, anyhow::Error> {
+    let entries = list(root, legacy)?;
+    for entry in &entries {
+        std::fs::remove_dir_all(&entry.path)
+            .with_context(|| format!("Can't remove {}", entry.path.display()))?;
+    }
+    Ok(entries)
+}
+
+/// Modification time of `path` (unix seconds).
+fn modified(path: &Path) -> Option<i64> {
+    let time = std::fs::metadata(path).ok()?.modified().ok()?;
+    Some(time.duration_since(std::time::UNIX_EPOCH).ok()?.as_secs() as i64)
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(Result::ok)
+        .map(|e| match e.file_type() {
+            Ok(t) if t.is_dir() => dir_size(&e.path()),
+            Ok(t) if t.is_file() => e.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
+
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{} {}", bytes, UNITS[0]),
+        _ => format!("{:.1} {}", size, UNITS[unit]),
+    }
+}
+
+pub fn format_age(secs: i64) -> String {
+    let hours = secs as f64 / 3600.0;
+    match hours < 48.0 {
+        true => format!("{:.1} hours", hours),
+        false => format!("{:.1} days", hours / 24.0),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::staging_dir::StagingDir;
+
+    /// Clone-like repository in `root/name` with an `origin` remote, and the
+    /// URL recorded unless `legacy`.
+    fn fake_clone(root: &Path, name: &str, legacy: bool, fetched: i64) -> PathBuf {
+        let path = root.join(name);
+        let repo = git2::Repository::init(&path).unwrap();
+        repo.remote("origin", &format!("https://example.com/{}", name)).unwrap();
+        if !legacy {
+            let mut config = repo.config().unwrap();
+            config.set_str("vibe-coded.url", &format!("https://example.com/{}", name)).unwrap();
+            config.set_i64("vibe-coded.fetched", fetched).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn test_list_prune_and_clear() {
+        let root = std::env::temp_dir().join(format!("vibe-coded-test-cache-{}", std::process::id()));
+        let dir = StagingDir::try_new(&root).unwrap();
+        let slugs = |entries: &[CacheEntry]| entries.iter().map(|e| e.slug.clone()).collect::<Vec<_>>();
+        fake_clone(&dir.path, "fresh", false, now());
+        fake_clone(&dir.path, "old", false, now() - 40 * 86400);
+        fake_clone(&dir.path, "legacy", true, 0);
+        git2::Repository::init(dir.path.join("unrelated")).unwrap();
+        std::fs::create_dir(dir.path.join("not-a-repo")).unwrap();
+
+        assert_eq!(slugs(&list(&dir.path, false).unwrap()), ["fresh", "old"]);
+        let entries = list(&dir.path, true).unwrap();
+        assert_eq!(slugs(&entries), ["fresh", "legacy", "old"]);
+        assert_eq!(entries[1].url, "https://example.com/legacy");
+        assert!(entries[1].age().is_some_and(|age| age < 3600));
+
+        let month = Duration::from_secs(30 * 86400);
+        assert_eq!(slugs(&prune(&dir.path, true, month).unwrap()), ["old"]);
+        assert!(!dir.path.join("old").exists());
+
+        assert_eq!(slugs(&clear(&dir.path, false).unwrap()), ["fresh"]);
+        assert!(dir.path.join("legacy").exists());
+        assert_eq!(slugs(&clear(&dir.path, true).unwrap()), ["legacy"]);
+        assert!(dir.path.join("unrelated").exists() && dir.path.join("not-a-repo").exists());
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+}
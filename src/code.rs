@@ -1,44 +1,90 @@
 use std::{collections::{HashMap, HashSet}, path::PathBuf};
-use cached::proc_macro::once;
 use git2::TreeWalkMode;
 use strsim::jaro_winkler;
 
-use crate::traits::Repo;
+use crate::{authors::{author_key, author_of, AuthorWalk}, error::{AnalysisError, Failure}, traits::Repo};
 
-pub fn average_code_insertions_per_commit(repo: &Repo) -> Option<f64> {
-    get_repowalk_data(repo)?.insertions
+pub fn average_code_insertions_per_commit(repo: &Repo) -> Result<f64, Failure> {
+    get_repowalk_data(repo)?.insertions.clone()
 }
-pub fn average_code_deletions_per_commit(repo: &Repo) -> Option<f64> {
-    get_repowalk_data(repo)?.deletions
+pub fn average_code_deletions_per_commit(repo: &Repo) -> Result<f64, Failure> {
+    get_repowalk_data(repo)?.deletions.clone()
 }
-pub fn average_time_between_commits(repo: &Repo) -> Option<f64> {
-    get_repowalk_data(repo)?.time_gaps.map(|v| v/3600.0)
+pub fn average_time_between_commits(repo: &Repo) -> Result<f64, Failure> {
+    get_repowalk_data(repo)?.time_gaps.clone().map(|v| v/3600.0)
 }
-pub fn average_lines_per_hour(repo: &Repo) -> Option<f64> {
+pub fn average_lines_per_hour(repo: &Repo) -> Result<f64, Failure> {
     let averages = get_repowalk_data(repo)?;
-    let ins = averages.insertions?;
-    let gaps = averages.time_gaps?;
-    Some(ins / (gaps / 3600.0))
+    let ins = averages.insertions.clone()?;
+    let gaps = averages.time_gaps.clone()?;
+    Ok(ins / (gaps / 3600.0))
+}
+
+/// Per-commit averages need this many commit pairs for [`mean_iqr`].
+const MIN_COMMIT_PAIRS: usize = 4;
+/// [`analyze_msg_similarity`] needs this many commit messages.
+const MIN_MESSAGES: usize = 5;
+
+/// Diff size of a single commit against its predecessor in the walk, or
+/// against the empty tree for a root commit.
+#[derive(Clone)]
+pub struct CommitStat {
+    pub id: git2::Oid,
+    pub summary: String,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+impl std::fmt::Display for CommitStat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let id = self.id.to_string();
+        write!(f, "{} +{} -{} {}", &id[..7], self.insertions, self.deletions, self.summary)
+    }
+}
+
+/// Commits shown as evidence for the per-commit averages.
+const EVIDENCE_COMMITS: usize = 5;
+
+pub fn largest_insertions(repo: &Repo) -> Vec<String> {
+    largest_commits(repo, |c| c.insertions)
+}
+pub fn largest_deletions(repo: &Repo) -> Vec<String> {
+    largest_commits(repo, |c| c.deletions)
+}
+fn largest_commits(repo: &Repo, key: fn(&CommitStat) -> usize) -> Vec<String> {
+    let Ok(data) = get_repowalk_data(repo) else {
+        return Vec::new();
+    };
+    let mut commits: Vec<&CommitStat> = data.commits.iter().collect();
+    commits.sort_by_key(|c| std::cmp::Reverse(key(c)));
+    commits.iter().take(EVIDENCE_COMMITS).map(|c| c.to_string()).collect()
 }
 
 #[derive(Clone)]
 pub struct WalkerResult {
-    pub insertions: Option<f64>,
-    pub deletions: Option<f64>,
-    pub time_gaps: Option<f64>,
+    pub insertions: Result<f64, Failure>,
+    pub deletions: Result<f64, Failure>,
+    pub time_gaps: Result<f64, Failure>,
     pub files: Vec<String>,
-    pub msg_similarity: Option<f64>,
+    pub msg_similarity: Result<f64, Failure>,
+    pub commits: Vec<CommitStat>,
+    /// Commits carrying an AI tool signature.
+    pub signed: Vec<crate::messages::SignedCommit>,
+    /// Number of commits walked.
+    pub walked: usize,
+    /// Author time of every walked commit, newest first.
+    pub times: Vec<crate::activity::CommitTime>,
+    /// Statistics per (mailmapped) author, most active first.
+    pub authors: Vec<crate::authors::AuthorStats>,
+}
 
+/// Revwalk statistics, computed once per [`crate::analysis::Analysis`].
+pub fn get_repowalk_data(repo: &Repo) -> Result<&WalkerResult, Failure> {
+    repo.analysis().walk.get_or_init(|| walk_repository(repo)).as_ref().map_err(Clone::clone)
 }
 
-#[once(sync_writes=true)]
-pub fn get_repowalk_data(repo: &Repo) -> Option<WalkerResult> {
-    let Ok(mut walker) = repo.revwalk() else {
-        return None
-    };
-    if walker.push_head().is_err() {
-        return None
-    };
+fn walk_repository(repo: &Repo) -> Result<WalkerResult, Failure> {
+    let mut walker = repo.walk_from_start().ok_or(AnalysisError::EmptyRepository)?;
 
 		let mut insertions: Vec<usize> = Vec::with_capacity(500) ;
 		let mut deletions: Vec<usize> = Vec::with_capacity(500) ;
@@ -46,22 +92,39 @@ pub fn get_repowalk_data(repo: &Repo) -> Option<WalkerResult> {
 		let mut next_commit = None;
 		let mut messages: Vec<String> = Vec::with_capacity(500);
 		let mut files_hs: HashSet<String> = HashSet::new();
+		let mut commits: Vec<CommitStat> = Vec::with_capacity(500);
+		let mut signed = Vec::new();
+		let mut walked = 0;
+		let mut times = Vec::with_capacity(500);
+		let mut authors: HashMap<String, AuthorWalk> = HashMap::new();
+		let mailmap = repo.mailmap().ok();
 
     while let Some(Ok(oid)) = walker.next() {
         let Ok(commit) = repo.find_commit(oid) else {
             continue;
         };
 
-        for te in commit.tree().unwrap().iter() {
+        let tree = commit.tree().map_err(|e| AnalysisError::read(format!("tree of {}", oid), e))?;
+        for te in tree.iter() {
             if let Some(p) = te.name() {
                 for p in p.split("/") {
                     files_hs.insert(p.to_lowercase());
                 }
             }
         }
+        let author = author_of(&commit, mailmap.as_ref());
+        let author_walk = authors.entry(author_key(&author)).or_default();
+        author_walk.seconds.push(author.when().seconds());
+        if author_walk.name.is_empty() {
+            author_walk.name = author.name().unwrap_or_default().to_string();
+        }
         if let Some(summary) = commit.summary() {
             messages.push(String::from(summary));
+            author_walk.messages.push(String::from(summary));
         }
+        signed.extend(crate::messages::ai_signatures(&commit));
+        times.push(crate::activity::CommitTime::of(&commit, &author));
+        walked += 1;
 
         if next_commit.is_none() {
             next_commit = Some(commit);
@@ -72,13 +135,22 @@ pub fn get_repowalk_data(repo: &Repo) -> Option<WalkerResult> {
 				// Stats block
 				{
 				let diff = repo.diff_tree_to_tree(
-    				commit.tree().ok().as_ref(),
+    				Some(&tree),
     				nc.tree().ok().as_ref(),
     				None
-				).unwrap();
-    				let stats = diff.stats().unwrap();
+				).map_err(|e| AnalysisError::read(format!("diff of {}", nc.id()), e))?;
+    				let stats = diff.stats().map_err(|e| AnalysisError::read(format!("diff stats of {}", nc.id()), e))?;
     				insertions.push(stats.insertions());
     				deletions.push(stats.deletions());
+    				let author_walk = authors.entry(author_key(&author_of(&nc, mailmap.as_ref()))).or_default();
+    				author_walk.insertions.push(stats.insertions());
+    				author_walk.deletions.push(stats.deletions());
+    				commits.push(CommitStat {
+    				    id: nc.id(),
+    				    summary: nc.summary().unwrap_or_default().to_string(),
+    				    insertions: stats.insertions(),
+    				    deletions: stats.deletions(),
+    				});
 				}
 
 				gaps.push((nc.time().seconds() - commit.time().seconds()) as usize);
@@ -86,21 +158,89 @@ pub fn get_repowalk_data(repo: &Repo) -> Option<WalkerResult> {
 
    				
     }
-    Some(WalkerResult {
-                insertions: mean_iqr(&mut insertions),
-                deletions: mean_iqr(&mut deletions),
-                time_gaps: mean_iqr(&mut gaps),
-                msg_similarity: analyze_msg_similarity(&messages),
+    if walked == 0 {
+        return Err(repo.no_commits());
+    }
+    // The oldest commit has no predecessor in the walk. A true root brought
+    // its whole tree in, a shallow boundary or the base of `--range` did not.
+    if let Some(root) = next_commit
+        && root.parent_count() == 0
+        && !repo.is_shallow_root(root.id())
+    {
+        let tree = root.tree().map_err(|e| AnalysisError::read(format!("tree of {}", root.id()), e))?;
+        let diff = repo
+            .diff_tree_to_tree(None, Some(&tree), None)
+            .map_err(|e| AnalysisError::read(format!("diff of {}", root.id()), e))?;
+        let stats = diff.stats().map_err(|e| AnalysisError::read(format!("diff stats of {}", root.id()), e))?;
+        insertions.push(stats.insertions());
+        deletions.push(stats.deletions());
+        let author_walk = authors.entry(author_key(&author_of(&root, mailmap.as_ref()))).or_default();
+        author_walk.insertions.push(stats.insertions());
+        author_walk.deletions.push(stats.deletions());
+        commits.push(CommitStat {
+            id: root.id(),
+            summary: root.summary().unwrap_or_default().to_string(),
+            insertions: stats.insertions(),
+            deletions: stats.deletions(),
+        });
+    }
+    let (diffs, pairs) = (insertions.len(), gaps.len());
+    let too_few_diffs = || Failure::missing(format!(
+        "fewer than {} commit diffs for mean_iqr, found {}", MIN_COMMIT_PAIRS, diffs
+    ));
+    let too_few_pairs = || Failure::missing(format!(
+        "fewer than {} commit pairs for mean_iqr, found {}", MIN_COMMIT_PAIRS, pairs
+    ));
+    Ok(WalkerResult {
+                insertions: mean_iqr(&mut insertions).ok_or_else(too_few_diffs),
+                deletions: mean_iqr(&mut deletions).ok_or_else(too_few_diffs),
+                time_gaps: mean_iqr(&mut gaps).ok_or_else(too_few_pairs),
+                msg_similarity: analyze_msg_similarity(&messages).ok_or_else(|| Failure::missing(format!(
+                    "fewer than {} commit messages for similarity, found {}", MIN_MESSAGES, messages.len()
+                ))),
                 files: files_hs.iter().cloned().collect(),
+                commits,
+                signed,
+                walked,
+                times,
+                authors: crate::authors::collect(authors),
     })
 
 }
 
+/// Paths changed by the commits of `--range`, `None` when the whole tree is analysed.
+pub fn touched_paths(repo: &Repo) -> Option<&HashSet<String>> {
+    repo.analysis().touched.get_or_init(|| collect_touched_paths(repo)).as_ref()
+}
+
+fn collect_touched_paths(repo: &Repo) -> Option<HashSet<String>> {
+    if !repo.is_range() {
+        return None;
+    }
+    let mut walker = repo.walk_from_start()?;
+    let mut paths = HashSet::new();
+    while let Some(Ok(oid)) = walker.next() {
+        let Ok(commit) = repo.find_commit(oid) else {
+            continue;
+        };
+        let parent_tree = commit.parent(0).ok().and_then(|p| p.tree().ok());
+        let Ok(diff) = repo.diff_tree_to_tree(parent_tree.as_ref(), commit.tree().ok().as_ref(), None) else {
+            continue;
+        };
+        for delta in diff.deltas() {
+            if let Some(path) = delta.new_file().path() {
+                paths.insert(path.to_string_lossy().into_owned());
+            }
+        }
+    }
+    Some(paths)
+}
+
 pub fn analyze_msg_similarity(messages: &[String]) -> Option<f64> {
     let mut messages = messages.to_vec();
     messages.sort_unstable();
 
-    if messages.len() < 5 {
+    if messages.len() < MIN_MESSAGES {
         return None;
     }
 
@@ -123,7 +263,7 @@ pub fn analyze_msg_similarity(messages: &[String]) -> Option<f64> {
     Some(mean * (1.0-iqr))
 }
 
-trait ToF64 {
+pub(crate) trait ToF64 {
     fn to_f64(self) -> f64;
 }
 
@@ -138,8 +278,8 @@ macro_rules! impl_to_f64 {
 }
 impl_to_f64!(usize, f64);
 
-fn mean_iqr<T: ToF64 + Copy + PartialOrd + std::iter::Sum>(data: &mut [T]) -> Option<f64> {
-    if data.len() < 4 {
+pub(crate) fn mean_iqr<T: ToF64 + Copy + PartialOrd + std::iter::Sum>(data: &mut [T]) -> Option<f64> {
+    if data.len() < MIN_COMMIT_PAIRS {
         return None;
     }
 
@@ -213,17 +353,21 @@ const COMMENT_MAP: [(&str, &str); 47] = [
     ("zsh", "#"),
 ];
 
-pub fn count_comment_ratio(repo: &Repo) -> Option<(usize, usize)> {
+pub fn count_comment_ratio(repo: &Repo) -> Result<(usize, usize), Failure> {
     let ext_map: HashMap<&str, &str> = HashMap::from(COMMENT_MAP);
+    let touched = touched_paths(repo);
     let mut line_count = 0;
     let mut comment_count = 0;
-    repo.head()
-        .ok()?
-        .peel_to_tree()
-        .ok()?
-        .walk(TreeWalkMode::PreOrder, |_, te| {
+    repo.start_tree()
+        .ok_or_else(|| repo.no_commits())?
+        .walk(TreeWalkMode::PreOrder, |root, te| {
             let _: Option<()> = (|| {
                 let path = PathBuf::from(te.name().unwrap());
+                if let Some(touched) = touched
+                    && !touched.contains(&format!("{}{}", root, te.name()?))
+                {
+                    return None;
+                }
                 if let Some(ext) = path.extension().and_then(|s| s.to_str())
                     && let Some(comment) = ext_map.get(ext)
                 {
@@ -240,6 +384,9 @@ pub fn count_comment_ratio(repo: &Repo) -> Option<(usize, usize)> {
             })();
             0
         })
-        .ok()?;
-    Some((comment_count, line_count))
+        .map_err(|e| AnalysisError::read("tree", e))?;
+    if line_count == 0 {
+        return Err(Failure::missing("no source files with a known comment marker"));
+    }
+    Ok((comment_count, line_count))
 }
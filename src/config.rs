@@ -0,0 +1,189 @@
+use std::{collections::HashMap, path::{Path, PathBuf}};
+
+use anyhow::{Context, anyhow};
+use serde::Deserialize;
+
+use crate::options::Options;
+
+pub const CONFIG_FILE: &str = "vibe-coded.toml";
+
+/// Contents of `vibe-coded.toml`.
+///
+/// ```toml
+/// cache_dir = "/var/cache/vibe-coded"
+///
+/// [rules.code-insertions-average]
+/// threshold = 400
+/// weight = 2
+///
+/// [rules.readme-emoji-count]
+/// enabled = false
+/// ```
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub cache_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub rules: HashMap<String, RuleConfig>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct RuleConfig {
+    pub threshold: Option<f64>,
+    pub weight: Option<f64>,
+    pub enabled: Option<bool>,
+}
+
+impl Config {
+    /// Loads `explicit` if given, otherwise the first config file found in
+    /// the current directory or the XDG config directory.
+    pub fn load(explicit: Option<&Path>) -> Result<Option<Self>, anyhow::Error> {
+        Self::load_from(explicit, &default_paths())
+    }
+
+    /// Loads `explicit`, or the first of `candidates` that exists.
+    fn load_from(explicit: Option<&Path>, candidates: &[PathBuf]) -> Result<Option<Self>, anyhow::Error> {
+        let path = match explicit {
+            Some(p) => p.to_path_buf(),
+            None => match candidates.iter().find(|p| p.is_file()).cloned() {
+                Some(p) => p,
+                None => return Ok(None),
+            },
+        };
+        let text = std::fs::read_to_string(&path)
+            .with_context(|| format!("Can't read config {}", path.display()))?;
+        let config: Config = toml::from_str(&text)
+            .with_context(|| format!("Invalid config {}", path.display()))?;
+        config.validate().with_context(|| format!("Invalid config {}", path.display()))?;
+        Ok(Some(config))
+    }
+
+    fn validate(&self) -> Result<(), anyhow::Error> {
+        let known = crate::rules::ids();
+        let mut unknown: Vec<&str> = self
+            .rules
+            .keys()
+            .map(String::as_str)
+            .filter(|id| !known.contains(id))
+            .collect();
+        if !unknown.is_empty() {
+            unknown.sort();
+            return Err(anyhow!(
+                "Unknown rule id(s): {} (known: {})",
+                unknown.join(", "),
+                known.join(", ")
+            ));
+        }
+        for (id, rule) in &self.rules {
+            if rule.weight.is_some_and(|w| w < 0.0) {
+                return Err(anyhow!("Weight for rule {} can't be negative", id));
+            }
+        }
+        Ok(())
+    }
+
+    /// Copies overrides into `options`, keeping values already set there
+    /// (command line flags win over the config file).
+    pub fn apply(&self, options: &mut Options) {
+        if options.cache_dir.is_none() {
+            options.cache_dir = self.cache_dir.clone();
+        }
+        for (id, rule) in &self.rules {
+            if let Some(threshold) = rule.threshold {
+                options.thresholds.entry(id.clone()).or_insert(threshold);
+            }
+            if let Some(weight) = rule.weight {
+                options.weights.entry(id.clone()).or_insert(weight);
+            }
+            if rule.enabled == Some(false) {
+                options.disabled.insert(id.clone());
+            }
+        }
+    }
+}
+
+fn default_paths() -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from(CONFIG_FILE)];
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")));
+    if let Some(dir) = config_home {
+        paths.push(dir.join("vibe-coded").join(CONFIG_FILE));
+    }
+    paths
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(text: &str) -> Result<Config, anyhow::Error> {
+        let config: Config = toml::from_str(text)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(parse("[rules.readme-length]\nthreshold = 500\nweight = 2\nenabled = true\n").is_ok());
+        let unknown = parse("[rules.no-such-rule]\nweight = 1\n").unwrap_err();
+        assert!(unknown.to_string().starts_with("Unknown rule id(s): no-such-rule"));
+        let negative = parse("[rules.readme-length]\nweight = -1\n").unwrap_err();
+        assert_eq!(negative.to_string(), "Weight for rule readme-length can't be negative");
+        assert!(parse("[rules.readme-length]\nthreshold = \"high\"\n").is_err());
+        assert!(parse("[rules.readme-length]\nlimit = 3\n").is_err());
+        assert!(parse("[rules.readme-length\n").is_err());
+    }
+
+    #[test]
+    fn test_apply_keeps_command_line_values() {
+        let config = parse(
+            "cache_dir = \"/from/config\"\n\
+             [rules.readme-length]\nthreshold = 500\nweight = 2\n\
+             [rules.readme-headings]\nthreshold = 10\n\
+             [rules.readme-emoji-count]\nenabled = false\n",
+        )
+        .unwrap();
+        let mut options = Options {
+            cache_dir: Some(PathBuf::from("/from/cli")),
+            thresholds: HashMap::from([(String::from("readme-length"), 800.0)]),
+            ..Default::default()
+        };
+        config.apply(&mut options);
+        assert_eq!(options.cache_dir, Some(PathBuf::from("/from/cli")));
+        assert_eq!(options.thresholds["readme-length"], 800.0);
+        assert_eq!(options.thresholds["readme-headings"], 10.0);
+        assert_eq!(options.weights["readme-length"], 2.0);
+        assert!(options.disabled.contains("readme-emoji-count"));
+        assert!(!options.disabled.contains("readme-length"));
+    }
+
+    #[test]
+    fn test_load_precedence() {
+        let root = std::env::temp_dir().join(format!("vibe-coded-test-config-{}", std::process::id()));
+        let dir = crate::staging_dir::StagingDir::try_new(&root).unwrap();
+        let write = |name: &str, threshold: u32| {
+            let path = dir.path.join(name);
+            std::fs::write(&path, format!("[rules.readme-length]\nthreshold = {}\n", threshold)).unwrap();
+            path
+        };
+        let threshold = |config: Option<Config>| config.unwrap().rules["readme-length"].threshold;
+        let explicit = write("explicit.toml", 1);
+        let cwd = write("cwd.toml", 2);
+        let xdg = write("xdg.toml", 3);
+        let missing = dir.path.join("missing.toml");
+
+        let candidates = [cwd.clone(), xdg.clone()];
+        assert_eq!(threshold(Config::load_from(Some(&explicit), &candidates).unwrap()), Some(1.0));
+        assert_eq!(threshold(Config::load_from(None, &candidates).unwrap()), Some(2.0));
+        assert_eq!(threshold(Config::load_from(None, &[missing.clone(), xdg]).unwrap()), Some(3.0));
+        assert!(Config::load_from(None, std::slice::from_ref(&missing)).unwrap().is_none());
+        assert!(Config::load_from(Some(&missing), &candidates).is_err());
+
+        let paths = default_paths();
+        assert_eq!(paths[0], PathBuf::from(CONFIG_FILE));
+        assert!(paths[1..].iter().all(|p| p.ends_with(Path::new("vibe-coded").join(CONFIG_FILE))));
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt::Display;
+
+/// Why a repository, or part of it, could not be analysed.
+#[derive(Clone, Debug)]
+pub enum AnalysisError {
+    CloneFailed { url: String, reason: String },
+    /// Fetching into an existing clone failed, `what` is the remote or ref.
+    FetchFailed { what: String, reason: String },
+    /// Filesystem error outside of git, e.g. creating the cache directory.
+    Io { path: String, reason: String },
+    RefNotFound(String),
+    EmptyRepository,
+    ObjectRead { object: String, reason: String },
+}
+
+impl AnalysisError {
+    pub(crate) fn read(object: impl Display, e: git2::Error) -> Self {
+        AnalysisError::ObjectRead {
+            object: object.to_string(),
+            reason: e.message().to_string(),
+        }
+    }
+}
+
+impl Display for AnalysisError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnalysisError::CloneFailed { url, reason } => write!(f, "can't clone {}: {}", url, reason),
+            AnalysisError::FetchFailed { what, reason } => write!(f, "can't fetch {}: {}", what, reason),
+            AnalysisError::Io { path, reason } => write!(f, "{}: {}", path, reason),
+            AnalysisError::RefNotFound(r) => write!(f, "ref '{}' not found", r),
+            AnalysisError::EmptyRepository => f.write_str("repository has no commits"),
+            AnalysisError::ObjectRead { object, reason } => write!(f, "can't read {}: {}", object, reason),
+        }
+    }
+}
+
+impl std::error::Error for AnalysisError {}
+
+/// Why a rule produced no verdict.
+#[derive(Clone, Debug)]
+pub enum Failure {
+    /// Not enough data to compute a value, with the cause.
+    Missing(String),
+    Error(AnalysisError),
+}
+
+impl Failure {
+    pub(crate) fn missing(cause: impl Into<String>) -> Self {
+        Failure::Missing(cause.into())
+    }
+
+    /// Short label for the report, the cause goes into the context column.
+    pub fn summary(&self) -> &'static str {
+        match self {
+            Failure::Missing(_) => "no value",
+            Failure::Error(_) => "error",
+        }
+    }
+}
+
+impl From<AnalysisError> for Failure {
+    fn from(e: AnalysisError) -> Self {
+        Failure::Error(e)
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Missing(cause) => f.write_str(cause),
+            Failure::Error(e) => e.fmt(f),
+        }
+    }
+}
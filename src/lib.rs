@@ -1,8 +1,15 @@
-use crate::{repo::{clone_repository,path_from_url}};
+use crate::{analysis::Analysis, error::Failure, options::Options, repo::{path_from_url,Target}, report::Report, rules::failed_result::FailedResult, traits::RuleResult};
 use anyhow::{Context};
 
 use rayon::prelude::*;
 
+mod activity;
+pub mod authors;
+mod analysis;
+pub mod batch;
+pub mod cache;
+pub mod config;
+pub mod error;
 mod rules;
 mod traits;
 mod rule_formatter;
@@ -10,26 +17,68 @@ mod staging_dir;
 mod readme;
 mod messages;
 mod code;
+pub mod options;
 pub mod repo;
+pub mod report;
 
-pub fn run_rules(url: &str) -> Result<(), anyhow::Error> {
+pub use rules::{describe, RuleInfo};
+pub use traits::{Category, Vibe};
 
-		// Needs to be done or all hell breaks loose on par_iter
-    let _= clone_repository(url).unwrap();
-		let mut results= rules::all().par_iter().map(|rule| rule.run_url(url)).collect::<Vec<_>>();
+pub fn run_rules(target: &Target, options: &Options) -> Result<Report, anyhow::Error> {
+    let rules: Vec<_> = rules::all().into_iter().filter(|rule| options.is_enabled(*rule)).collect();
+    let whole_tree = match options.range {
+        Some(_) => rules.iter().filter(|rule| !rule.range_aware()).map(|rule| rule.id()).collect(),
+        None => Vec::new(),
+    };
+    let mut report = Report {
+        target: target.to_string(),
+        git_ref: options.git_ref.clone(),
+        range: options.range.clone(),
+        whole_tree,
+        head: None,
+        shallow: false,
+        fetched: None,
+        explain: rules.iter().map(|rule| rule.id()).filter(|id| options.explains(id)).collect(),
+        authors: Vec::new(),
+        results: Vec::new(),
+        weights: rules.iter().map(|rule| (rule.id(), options.weight(*rule))).collect(),
+    };
+
+		// Clone/open once up front, rules then share the analysis context.
+		// Clone, fetch, ref and empty-repository errors fail every rule.
+    let analysis = match Analysis::new(target, options) {
+        Ok(analysis) => analysis,
+        Err(e) => {
+            let failure = Failure::from(e);
+            report.results = rules
+                .iter()
+                .map(|rule| Box::new(FailedResult { name: rule.id(), failure: failure.clone() }) as Box<dyn RuleResult>)
+                .collect();
+            report.results.sort_by_key(|a| a.name());
+            return Ok(report);
+        }
+    };
+    report.head = analysis.state.head.map(|oid| oid.to_string());
+    report.shallow = !analysis.shallow_roots.is_empty();
+    report.fetched = analysis.fetched;
+    if options.authors {
+        let authors = analysis.open().map_err(Failure::from).and_then(|repo| Ok(authors::get_authors(&repo)?.to_vec()));
+        match authors {
+            Ok(authors) => report.authors = authors,
+            Err(failure) => eprintln!("Can't compute per-author statistics: {}", failure),
+        }
+    }
+		let mut results= rules.par_iter().map(|rule| rule.run_shared(&analysis)).collect::<Vec<_>>();
 		results.sort_by_key(|a| a.name());
 		//results.sort_by_key(|a| a.is_vibe());
-		for r in results {
-    		r.render();
-		}
-
-    Ok(())
+    report.results = results;
+    Ok(report)
 }
 
-pub fn clean_repo_dir(url: &str) -> Result<(), anyhow::Error> {
-    let path = path_from_url(url);
+pub fn clean_repo_dir(url: &str, options: &Options) -> Result<(), anyhow::Error> {
+    let path = path_from_url(url, options);
     if path.exists() {
-        println!("Cleaning: {}", &path.to_string_lossy());
+        eprintln!("Cleaning: {}", &path.to_string_lossy());
         std::fs::remove_dir_all(&path).context("Failed to clean repo dir")
     } else {
         Ok(())
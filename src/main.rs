@@ -1,40 +1,249 @@
-use std::{io, process::exit};
+use std::{ffi::OsString, fs::File, io::BufReader, path::PathBuf, process::exit};
 
 use anyhow::{Context, anyhow};
-use vibe_coded::{clean_repo_dir, run_rules};
+use vibe_coded::{batch::{read_targets, run_batch}, cache::{self, CacheEntry}, clean_repo_dir, config::Config, describe, options::{glob_match, parse_duration, parse_weight, Options}, repo::{cache_root, default_cache_root, Target}, report::Format, run_rules};
 use regex::RegexSet;
 use once_cell::sync::Lazy;
 
+/// Analysis ran and the repository looks hand-made.
+const EXIT_CLEAN: i32 = 0;
+/// Analysis ran and the repository crossed `--fail-above` / `--fail-on`.
+const EXIT_VIBE: i32 = 1;
+/// Bad arguments, or the repository could not be analysed at all.
+const EXIT_ERROR: i32 = 2;
+
+/// Repositories analysed at once in `--batch` mode.
+const DEFAULT_JOBS: usize = 4;
+
+fn or_exit<T, E: std::fmt::Display>(result: Result<T, E>) -> T {
+    result.unwrap_or_else(|e| {
+        eprintln!("Error: {:#}", e);
+        exit(EXIT_ERROR);
+    })
+}
+
 fn main() {
-    let mut args = pico_args::Arguments::from_env();
-    let url = args.free_from_str::<String>()
-        .or_else(|_e| {
-            println!("No URL provided. Checking clipboard...");
-            get_from_clip()
-        })
-        .unwrap_or_else(|e| {
-            eprintln!("Error: {}", e.to_string());
-            eprintln!("\nRepo missing, provide argument or keep in clipboard");
-            exit(1);
-        });
+    let mut raw: Vec<OsString> = std::env::args_os().skip(1).collect();
+    if raw.first().is_some_and(|a| a == "cache") {
+        raw.remove(0);
+        run_cache(pico_args::Arguments::from_vec(raw));
+    }
+    let explain = take_explain(&mut raw);
+    let mut args = pico_args::Arguments::from_vec(raw);
     let clean_before = args.contains(["-c", "--clean"]);
-    if clean_before {
-        let _ = clean_repo_dir(&url);
+    let format = or_exit(args.opt_value_from_str::<_, Format>("--format")).unwrap_or_default();
+    let weights = or_exit(args.values_from_fn("--weight", parse_weight));
+    let fail_above = or_exit(args.opt_value_from_str::<_, usize>("--fail-above"));
+    let fail_on = split_list(or_exit(args.opt_value_from_str::<_, String>("--fail-on")));
+    let list_rules = args.contains("--list-rules");
+    let only = or_exit(args.values_from_str::<_, String>("--only"));
+    let skip = or_exit(args.values_from_str::<_, String>("--skip"));
+    let git_ref = or_exit(args.opt_value_from_str::<_, String>("--ref"));
+    let range = or_exit(args.opt_value_from_str::<_, String>("--range"));
+    let depth = or_exit(args.opt_value_from_str::<_, u32>("--depth"));
+    let full_history = args.contains("--full-history");
+    let refresh = args.contains("--refresh");
+    let authors = args.contains("--authors");
+    let cache_ttl = or_exit(args.opt_value_from_fn("--ttl", parse_duration));
+    let cache_dir = or_exit(args.opt_value_from_str::<_, PathBuf>("--cache-dir"));
+    let config_path = or_exit(args.opt_value_from_str::<_, PathBuf>("--config"));
+    let mut options = Options {
+        weights: weights.into_iter().collect(),
+        fail_above,
+        fail_on,
+        only: split_list(only),
+        skip: split_list(skip),
+        git_ref,
+        range,
+        depth,
+        full_history,
+        refresh,
+        cache_ttl,
+        cache_dir,
+        explain,
+        authors,
+        ..Default::default()
+    };
+    if let Some(config) = or_exit(Config::load(config_path.as_deref())) {
+        config.apply(&mut options);
+    }
+    or_exit(options.validate());
+    if list_rules {
+        print_rules(&options, format);
+        exit(EXIT_CLEAN);
+    }
+    let batch = or_exit(args.opt_value_from_str::<_, String>("--batch"));
+    let jobs = or_exit(args.opt_value_from_str::<_, usize>("--jobs")).unwrap_or(DEFAULT_JOBS);
+    if let Some(source) = batch {
+        let targets = or_exit(match source.as_str() {
+            "-" => read_targets(std::io::stdin().lock()),
+            path => File::open(path)
+                .with_context(|| format!("Can't open batch file {}", path))
+                .and_then(|f| read_targets(BufReader::new(f))),
+        });
+        if clean_before {
+            for target in &targets {
+                if let Target::Remote(url) = target {
+                    let _ = clean_repo_dir(url, &options);
+                }
+            }
+        }
+        let report = or_exit(run_batch(&targets, &options, jobs));
+        or_exit(report.print(format));
+        for entry in &report.entries {
+            if let Ok(r) = &entry.result {
+                for warning in r.unchecked_gates(&options) {
+                    eprintln!("Warning: {}: {}", entry.target, warning);
+                }
+            }
+        }
+        if report.exceeds(&options) {
+            exit(EXIT_VIBE);
+        }
+        if report.has_errors() {
+            exit(EXIT_ERROR);
+        }
+        exit(EXIT_CLEAN);
+    }
+    let local_path = or_exit(args.opt_value_from_str::<_, PathBuf>("--path"));
+    let target = match local_path {
+        Some(path) => Target::Local(path),
+        None => args.free_from_str::<String>()
+            .map(|t| Target::parse(&t))
+            .or_else(|_e| {
+                eprintln!("No URL provided. Checking clipboard...");
+                get_from_clip().map(Target::Remote)
+            })
+            .unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                eprintln!("\nRepo missing, provide argument or keep in clipboard");
+                exit(EXIT_ERROR);
+            }),
+    };
+    if clean_before && let Target::Remote(url) = &target {
+        let _ = clean_repo_dir(url, &options);
+    };
+    let report = or_exit(run_rules(&target, &options));
+    or_exit(report.print(format));
+    for warning in report.unchecked_gates(&options) {
+        eprintln!("Warning: {}", warning);
+    }
+    if report.exceeds(&options) {
+        exit(EXIT_VIBE);
+    }
+    if !report.has_results() {
+        eprintln!("Error: no rule produced a result");
+        exit(EXIT_ERROR);
+    }
+    exit(EXIT_CLEAN);
+}
+
+/// `vibe-coded cache list|prune --older-than <duration>|clear`
+fn run_cache(mut args: pico_args::Arguments) -> ! {
+    let format = or_exit(args.opt_value_from_str::<_, Format>("--format")).unwrap_or_default();
+    let cache_dir = or_exit(args.opt_value_from_str::<_, PathBuf>("--cache-dir"));
+    let config_path = or_exit(args.opt_value_from_str::<_, PathBuf>("--config"));
+    let older_than = or_exit(args.opt_value_from_fn("--older-than", parse_duration));
+    let mut options = Options { cache_dir, ..Default::default() };
+    if let Some(config) = or_exit(Config::load(config_path.as_deref())) {
+        config.apply(&mut options);
+    }
+    let root = cache_root(&options);
+    let legacy = root == default_cache_root();
+    let command = or_exit(args.free_from_str::<String>().context("Missing cache command, expected list, prune or clear"));
+    let entries = or_exit(match (command.as_str(), older_than) {
+        ("list", _) => cache::list(&root, legacy),
+        ("prune", Some(older_than)) => cache::prune(&root, legacy, older_than),
+        ("prune", None) => Err(anyhow!("prune needs --older-than <duration>, e.g. 30d")),
+        ("clear", _) => cache::clear(&root, legacy),
+        (other, _) => Err(anyhow!("Unknown cache command '{}', expected list, prune or clear", other)),
+    });
+    if format == Format::Json {
+        println!("{}", or_exit(serde_json::to_string_pretty(&entries)));
+        exit(EXIT_CLEAN);
+    }
+    if command != "list" {
+        let freed: u64 = entries.iter().map(|e| e.size).sum();
+        for entry in &entries {
+            println!("Removed {}", entry.slug);
+        }
+        println!("Removed {} clone(s), freed {}", entries.len(), cache::format_size(freed));
+        exit(EXIT_CLEAN);
+    }
+    eprintln!("Cache: {}", root.display());
+    for entry in &entries {
+        print_cache_entry(entry);
+    }
+    exit(EXIT_CLEAN);
+}
+
+fn print_cache_entry(entry: &CacheEntry) {
+    let fetched = match entry.age() {
+        Some(age) => format!("{} ago", cache::format_age(age)),
+        None => String::from("unknown"),
     };
-    let _ = run_rules(&url);
+    println!(
+        "{:50} {:>10} {:>16}  {}",
+        entry.slug,
+        cache::format_size(entry.size),
+        fetched,
+        entry.url
+    );
 }
 
+/// Pulls `--explain [<rule-id>]` out of the arguments. The value is optional,
+/// so the next argument is only taken when it names (or globs) a rule.
+fn take_explain(args: &mut Vec<OsString>) -> Option<Vec<String>> {
+    let ids: Vec<&str> = describe(&Options::default()).into_iter().map(|r| r.id).collect();
+    let mut explain = None;
+    while let Some(pos) = args.iter().position(|a| a == "--explain" || a.to_string_lossy().starts_with("--explain=")) {
+        let arg = args.remove(pos).to_string_lossy().into_owned();
+        let patterns: &mut Vec<String> = explain.get_or_insert_with(Vec::new);
+        if let Some((_, value)) = arg.split_once('=') {
+            patterns.push(value.to_string());
+            continue;
+        }
+        let names_rule = |a: &OsString| {
+            let a = a.to_string_lossy();
+            a.split(',').all(|p| ids.iter().any(|id| glob_match(p.trim(), id)))
+        };
+        if args.get(pos).is_some_and(names_rule) {
+            patterns.push(args.remove(pos).to_string_lossy().into_owned());
+        }
+    }
+    explain.map(split_list)
+}
 
-const GIT_URL_WORDS: &[&str] = &[
-    "github",
-];
+/// Flattens repeated and comma separated values (`--only a,b --only c`).
+fn split_list(values: impl IntoIterator<Item = String>) -> Vec<String> {
+    values
+        .into_iter()
+        .flat_map(|v| v.split(',').map(|s| s.trim().to_string()).collect::<Vec<_>>())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn print_rules(options: &Options, format: Format) {
+    let rules = describe(options);
+    if format == Format::Json {
+        println!("{}", or_exit(serde_json::to_string_pretty(&rules)));
+        return;
+    }
+    for rule in rules {
+        let disabled = if rule.enabled { "" } else { " [disabled]" };
+        println!(
+            "{:25} {:10} {} (threshold: {}, weight: {}){}",
+            rule.id, rule.category, rule.description, rule.threshold, rule.weight, disabled
+        );
+    }
+}
 
 fn get_from_clip() -> Result<String, anyhow::Error> {
     let mut clip = arboard::Clipboard::new().context("Can't fetch Clipboard")?;
     let text = clip.get_text().context("No text in clipboard")?.to_lowercase();
     if validate_url(&text) {
-        println!("Found git-like URL in clipboard: {}", &text);
-        Ok(String::from(text))
+        eprintln!("Found git-like URL in clipboard: {}", &text);
+        Ok(text)
     } else {
         Err(anyhow!("Not a recognized URL in Clipboard"))
     }
@@ -61,3 +270,31 @@ fn validate_url(url: &str) -> bool {
 
     REPO_SET.is_match(url)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn explain(args: &[&str]) -> (Option<Vec<String>>, Vec<String>) {
+        let mut args: Vec<OsString> = args.iter().map(OsString::from).collect();
+        let explain = take_explain(&mut args);
+        (explain, args.iter().map(|a| a.to_string_lossy().into_owned()).collect())
+    }
+
+    #[test]
+    fn test_take_explain() {
+        assert_eq!(explain(&["."]), (None, vec![String::from(".")]));
+        assert_eq!(explain(&["--explain", "."]), (Some(Vec::new()), vec![String::from(".")]));
+        assert_eq!(explain(&[".", "--explain"]), (Some(Vec::new()), vec![String::from(".")]));
+        assert_eq!(
+            explain(&["--explain", "readme-length", "https://github.com/a/b"]),
+            (Some(vec![String::from("readme-length")]), vec![String::from("https://github.com/a/b")])
+        );
+        assert_eq!(
+            explain(&["--explain=readme-*,code-*", "--explain", "repository-age", "."]).0,
+            Some(vec![String::from("readme-*"), String::from("code-*"), String::from("repository-age")])
+        );
+        // Only values that name a rule are taken, `readme` alone matches none.
+        assert_eq!(explain(&["--explain", "readme"]), (Some(Vec::new()), vec![String::from("readme")]));
+    }
+}
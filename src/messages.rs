@@ -1,6 +1,152 @@
-use crate::{code::get_repowalk_data, traits::Repo};
+use git2::Oid;
+use once_cell::sync::Lazy;
+use regex::Regex;
 
-pub fn get_message_similarities(repo: &Repo) -> Option<f64> {
+use crate::{code::get_repowalk_data, error::Failure, traits::Repo};
+
+/// Names of AI coding tools as they show up in trailers, signatures and bot accounts.
+const AI_TOOLS: &str = r"claude|anthropic|copilot|chatgpt|openai|gpt-?\d\w*|gemini|cursor|aider|codex|devin|windsurf|codeium|tabnine";
+
+/// Trailer keys that credit a tool as co-author or generator.
+const AI_TRAILER_KEYS: [&str; 4] = ["co-authored-by", "generated-by", "assisted-by", "ai-assisted-by"];
+
+/// Addresses AI tools commit or co-author with, no person uses them.
+const AI_ADDRESSES: [&str; 3] = ["noreply@anthropic.com", "cursoragent@cursor.com", "aider@aider.chat"];
+
+/// Whole tool names only, so `Claudette` or `cursory` don't count.
+static TOOL: Lazy<Regex> = Lazy::new(|| Regex::new(&format!(r"(?i)\b({})\b", AI_TOOLS)).unwrap());
+
+/// Commit whose message or identities carry an AI tool signature.
+#[derive(Clone)]
+pub struct SignedCommit {
+    pub id: Oid,
+    pub summary: String,
+    /// What gave the tool away, e.g. `Co-authored-by: Claude <noreply@anthropic.com>`.
+    pub signatures: Vec<String>,
+}
+
+pub fn get_message_similarities(repo: &Repo) -> Result<f64, Failure> {
+    let data = get_repowalk_data(repo)?;
+    data.msg_similarity.clone()
+}
+
+/// Share of walked commits with an AI tool signature, with the signed commits.
+pub fn get_ai_signatures(repo: &Repo) -> Result<(f64, Vec<SignedCommit>), Failure> {
     let data = get_repowalk_data(repo)?;
-    data.msg_similarity
+    Ok((data.signed.len() as f64 / data.walked as f64, data.signed.clone()))
+}
+
+/// Tool signatures in the message, trailers, author and committer of `commit`.
+pub fn ai_signatures(commit: &git2::Commit) -> Option<SignedCommit> {
+    static GENERATED: Lazy<Regex> = Lazy::new(|| {
+        Regex::new(&format!(r"(?i)\b(generated|created|written|made) (with|by|using)\W+({})\b", AI_TOOLS)).unwrap()
+    });
+    static IDENTITY: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(.*?)\s*<([^<>]*)>\s*$").unwrap());
+    let message = commit.message().unwrap_or_default();
+    let summary = commit.summary().unwrap_or_default();
+    let mut signatures = Vec::new();
+
+    if summary.to_lowercase().starts_with("aider: ") {
+        signatures.push(String::from("aider: prefix"));
+    }
+    if let Ok(trailers) = git2::message_trailers_strs(message) {
+        for (key, value) in trailers.iter() {
+            let key_lower = key.to_lowercase();
+            if !AI_TRAILER_KEYS.contains(&key_lower.as_str()) {
+                continue;
+            }
+            // Co-authors are people unless the address says otherwise, the
+            // other keys credit a tool and may just name it.
+            let tool = match IDENTITY.captures(value) {
+                Some(identity) => is_ai_identity(&identity[1], &identity[2]),
+                None => key_lower != "co-authored-by" && TOOL.is_match(value),
+            };
+            if tool {
+                signatures.push(format!("{}: {}", key, value));
+            }
+        }
+    }
+    for line in message.lines() {
+        if GENERATED.is_match(line) {
+            signatures.push(line.trim().to_string());
+        }
+    }
+    for (role, identity) in [("author", commit.author()), ("committer", commit.committer())] {
+        let name = identity.name().unwrap_or_default();
+        let email = identity.email().unwrap_or_default();
+        if is_ai_identity(name, email) {
+            signatures.push(format!("{}: {} <{}>", role, name, email));
+        }
+    }
+    signatures.dedup();
+    (!signatures.is_empty()).then(|| SignedCommit {
+        id: commit.id(),
+        summary: summary.to_string(),
+        signatures,
+    })
+}
+
+/// GitHub app identities like `dependabot[bot]`.
+pub fn is_bot(identity: &git2::Signature) -> bool {
+    is_bot_account(identity.name().unwrap_or_default(), identity.email().unwrap_or_default())
+}
+
+fn is_bot_account(name: &str, email: &str) -> bool {
+    name.ends_with("[bot]") || email.contains("[bot]")
+}
+
+/// Identity of an AI tool: one of [`AI_ADDRESSES`], the Copilot agent's
+/// GitHub address, or a bot account named after a tool. People who merely
+/// share a name with a tool (`Devin Smith`) or work at its vendor don't count.
+fn is_ai_identity(name: &str, email: &str) -> bool {
+    let email = email.to_lowercase();
+    AI_ADDRESSES.contains(&email.as_str())
+        || email.ends_with("+copilot@users.noreply.github.com")
+        || (is_bot_account(name, &email) && (TOOL.is_match(name) || TOOL.is_match(&email)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::staging_dir::StagingDir;
+
+    #[test]
+    fn test_ai_signatures() {
+        let root = std::env::temp_dir().join(format!("vibe-coded-test-signatures-{}", std::process::id()));
+        let dir = StagingDir::try_new(&root).unwrap();
+        let repo = git2::Repository::init(&dir.path).unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        let human = git2::Signature::now("Jane", "jane@example.com").unwrap();
+        let bot = git2::Signature::now("copilot-swe-agent[bot]", "198982749+Copilot@users.noreply.github.com").unwrap();
+        let dependabot = git2::Signature::now("dependabot[bot]", "49699333+dependabot[bot]@users.noreply.github.com").unwrap();
+        let commit = |author: &git2::Signature, message: &str| {
+            let oid = repo.commit(None, author, author, message, &tree, &[]).unwrap();
+            ai_signatures(&repo.find_commit(oid).unwrap()).map(|s| s.signatures)
+        };
+
+        assert_eq!(
+            commit(&human, "Fix parser\n\nGenerated with [Claude Code](https://claude.ai/code)\n\nCo-Authored-By: Claude <noreply@anthropic.com>\n"),
+            Some(vec![
+                String::from("Co-Authored-By: Claude <noreply@anthropic.com>"),
+                String::from("Generated with [Claude Code](https://claude.ai/code)"),
+            ])
+        );
+        assert_eq!(commit(&human, "aider: Add tests"), Some(vec![String::from("aider: prefix")]));
+        assert!(commit(&bot, "Initial plan").is_some());
+        assert_eq!(commit(&dependabot, "Bump serde"), None);
+        assert_eq!(commit(&human, "Move cursor handling\n\nCo-authored-by: Bob <bob@example.com>"), None);
+
+        let copilot = git2::Signature::now("Copilot", "198982749+Copilot@users.noreply.github.com").unwrap();
+        assert!(commit(&copilot, "Fix lint").is_some());
+        assert_eq!(
+            commit(&human, "Port to gpt-4o\n\nAssisted-by: GPT-4o\n"),
+            Some(vec![String::from("Assisted-by: GPT-4o")])
+        );
+        // People named like a tool, or working at its vendor, are not tools.
+        assert_eq!(commit(&human, "Add parser\n\nCo-authored-by: Devin Smith <devin.smith@example.com>\n"), None);
+        assert_eq!(commit(&human, "Add parser\n\nCo-authored-by: Claude Shannon <claude@example.com>\n"), None);
+        assert_eq!(commit(&human, "Tidy up\n\nWritten by Claudette\n"), None);
+        let employee = git2::Signature::now("Sam Human", "sam@anthropic.com").unwrap();
+        assert_eq!(commit(&employee, "Fix typo"), None);
+    }
 }
@@ -0,0 +1,213 @@
+use std::{collections::{HashMap, HashSet}, path::PathBuf, time::Duration};
+
+use anyhow::anyhow;
+use regex::Regex;
+
+use crate::traits::Rule;
+
+/// Clone depth used unless `--depth` or `--full-history` says otherwise.
+pub const DEFAULT_CLONE_DEPTH: u32 = 100;
+
+/// Age after which a cached clone is fetched again.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(24 * 3600);
+
+/// Settings shared by every rule of a single run.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// Per-rule weight overrides, keyed by rule id.
+    pub weights: HashMap<String, f64>,
+    /// Per-rule threshold overrides, keyed by rule id.
+    pub thresholds: HashMap<String, f64>,
+    /// Rule ids that are not run at all.
+    pub disabled: HashSet<String>,
+    /// Glob patterns of rule ids to run, all rules when empty.
+    pub only: Vec<String>,
+    /// Glob patterns of rule ids to leave out.
+    pub skip: Vec<String>,
+    /// Branch, tag or commit to analyse instead of HEAD.
+    pub git_ref: Option<String>,
+    /// `base..tip` commit range to analyse instead of the whole history.
+    pub range: Option<String>,
+    /// Number of commits to clone, `DEFAULT_CLONE_DEPTH` when not set.
+    pub depth: Option<u32>,
+    /// Clone (or unshallow a cached clone to) the complete history.
+    pub full_history: bool,
+    /// Fetch cached clones regardless of their age.
+    pub refresh: bool,
+    /// Age after which cached clones are fetched, `DEFAULT_CACHE_TTL` when not set.
+    pub cache_ttl: Option<Duration>,
+    /// Directory holding cached clones, see [`crate::repo::cache_root`].
+    pub cache_dir: Option<PathBuf>,
+    /// Overall score (percent) above which the run counts as vibe-coded.
+    pub fail_above: Option<usize>,
+    /// Rule ids that mark the run as vibe-coded when any of them says VIBE.
+    pub fail_on: Vec<String>,
+    /// Glob patterns of rules whose evidence is shown, all rules when empty,
+    /// no evidence when `None`.
+    pub explain: Option<Vec<String>>,
+    /// Add the per-author statistics table to the report.
+    pub authors: bool,
+}
+
+impl Options {
+    pub fn weight(&self, rule: &dyn Rule) -> f64 {
+        self.weights.get(rule.id()).copied().unwrap_or_else(|| rule.weight())
+    }
+
+    pub fn threshold(&self, rule: &dyn Rule) -> f64 {
+        self.thresholds.get(rule.id()).copied().unwrap_or_else(|| rule.threshold())
+    }
+
+    pub fn is_enabled(&self, rule: &dyn Rule) -> bool {
+        let id = rule.id();
+        !self.disabled.contains(id)
+            && (self.only.is_empty() || self.only.iter().any(|p| glob_match(p, id)))
+            && !self.skip.iter().any(|p| glob_match(p, id))
+    }
+
+    /// Whether `--explain` asks for the evidence of rule `id`.
+    pub fn explains(&self, id: &str) -> bool {
+        match &self.explain {
+            None => false,
+            Some(patterns) => patterns.is_empty() || patterns.iter().any(|p| glob_match(p, id)),
+        }
+    }
+
+    /// Depth as passed to libgit2, where 0 means the complete history.
+    pub fn clone_depth(&self) -> i32 {
+        match self.full_history {
+            true => 0,
+            false => self.depth.unwrap_or(DEFAULT_CLONE_DEPTH).min(i32::MAX as u32) as i32,
+        }
+    }
+
+    /// Both sides of `--range`, `None` when not given or malformed.
+    pub fn range_ends(&self) -> Option<(&str, &str)> {
+        let (base, tip) = self.range.as_deref()?.split_once("..")?;
+        if base.is_empty() || tip.is_empty() || tip.starts_with('.') {
+            return None;
+        }
+        Some((base, tip))
+    }
+
+    /// Fails on rule ids that are not registered.
+    pub fn validate(&self) -> Result<(), anyhow::Error> {
+        if self.depth == Some(0) {
+            return Err(anyhow!("--depth must be at least 1, use --full-history for the complete history"));
+        }
+        if self.depth.is_some() && self.full_history {
+            return Err(anyhow!("--depth and --full-history can't be used together"));
+        }
+        if let Some(range) = &self.range {
+            if self.range_ends().is_none() {
+                return Err(anyhow!("Invalid range '{}', expected <base>..<tip>", range));
+            }
+            if self.git_ref.is_some() {
+                return Err(anyhow!("--ref and --range can't be used together"));
+            }
+        }
+        let known = crate::rules::ids();
+        let mut unknown: Vec<&str> = self
+            .weights
+            .keys()
+            .chain(self.thresholds.keys())
+            .chain(&self.disabled)
+            .chain(&self.fail_on)
+            .map(String::as_str)
+            .filter(|id| !known.contains(id))
+            .collect();
+        unknown.sort();
+        unknown.dedup();
+        if !unknown.is_empty() {
+            return Err(anyhow!(
+                "Unknown rule id(s): {} (known: {})",
+                unknown.join(", "),
+                known.join(", ")
+            ));
+        }
+        if let Some(pattern) = self
+            .only
+            .iter()
+            .chain(&self.skip)
+            .chain(self.explain.iter().flatten())
+            .find(|p| !known.iter().any(|id| glob_match(p, id)))
+        {
+            return Err(anyhow!("Pattern '{}' matches no rule (known: {})", pattern, known.join(", ")));
+        }
+        Ok(())
+    }
+}
+
+/// Parses `rule-id=weight` as given to `--weight`.
+pub fn parse_weight(s: &str) -> Result<(String, f64), anyhow::Error> {
+    let (id, weight) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Expected <rule-id>=<weight>, got '{}'", s))?;
+    let weight: f64 = weight
+        .parse()
+        .map_err(|_| anyhow!("Invalid weight '{}' for rule {}", weight, id))?;
+    if weight < 0.0 {
+        return Err(anyhow!("Weight for rule {} can't be negative", id));
+    }
+    Ok((id.to_string(), weight))
+}
+
+/// Parses durations like `90s`, `15m`, `12h`, `30d` or `2w`.
+pub fn parse_duration(s: &str) -> Result<Duration, anyhow::Error> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (amount, unit) = s.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| anyhow!("Invalid duration '{}', expected e.g. 12h or 30d", s))?;
+    let unit_secs = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 24 * 3600,
+        "w" => 7 * 24 * 3600,
+        "" => return Err(anyhow!("Missing unit in duration '{}', use s, m, h, d or w, e.g. {}h", s, amount)),
+        _ => return Err(anyhow!("Unknown unit '{}' in duration '{}', use s, m, h, d or w", unit, s)),
+    };
+    Ok(Duration::from_secs(amount * unit_secs))
+}
+
+/// Matches `text` against a shell-like pattern, where `*` is any run of
+/// characters and `?` a single one.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut re = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '*' => re.push_str(".*"),
+            '?' => re.push('.'),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    re.push('$');
+    Regex::new(&re).is_ok_and(|r| r.is_match(text))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("readme-*", "readme-emoji-count"));
+        assert!(glob_match("code-?ap-per-commit", "code-gap-per-commit"));
+        assert!(glob_match("repository-age", "repository-age"));
+        assert!(!glob_match("readme-*", "code-comment-ratio"));
+        assert!(!glob_match("repository", "repository-age"));
+        assert!(!glob_match("code.*", "code-gap-per-commit"));
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90s").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("12h").unwrap(), Duration::from_secs(12 * 3600));
+        assert_eq!(parse_duration("30d").unwrap(), Duration::from_secs(30 * 24 * 3600));
+        assert!(parse_duration("d").is_err());
+        assert!(parse_duration("3y").is_err());
+        assert!(parse_duration("12").is_err());
+    }
+}
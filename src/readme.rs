@@ -1,19 +1,195 @@
-use std::{collections::{HashMap, HashSet}, path::PathBuf};
 use unicode_segmentation::UnicodeSegmentation;
-use pulldown_cmark::{Parser, Event, Tag};
+use once_cell::sync::Lazy;
+use pulldown_cmark::{Parser, Event, Tag, TagEnd};
+use regex::Regex;
+use std::ops::Range;
 
-use crate::traits::Repo;
+use crate::{error::{AnalysisError, Failure}, traits::Repo};
 
-pub fn get_readme(repo: &Repo) -> Option<String>{
-    let blob = repo
-        .head().ok()?
-        .peel_to_tree().ok()?
-        .get_path(&PathBuf::from("README.md")).ok()?
-        .to_object(repo).ok()?
-        .peel_to_blob().ok()?
-        ;
-        String::from_utf8(blob.content().to_vec()).ok()
+/// Directories searched for a README, in GitHub's order.
+const README_DIRS: [&str; 3] = [".github", "", "docs"];
 
+/// README extensions by preference when a directory has more than one.
+const README_EXTENSIONS: [(&str, ReadmeFormat); 9] = [
+    ("md", ReadmeFormat::Markdown),
+    ("markdown", ReadmeFormat::Markdown),
+    ("mdown", ReadmeFormat::Markdown),
+    ("rst", ReadmeFormat::Rst),
+    ("adoc", ReadmeFormat::AsciiDoc),
+    ("asciidoc", ReadmeFormat::AsciiDoc),
+    ("org", ReadmeFormat::Org),
+    ("txt", ReadmeFormat::Text),
+    ("", ReadmeFormat::Text),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReadmeFormat {
+    Markdown,
+    Rst,
+    AsciiDoc,
+    Org,
+    Text,
+}
+
+pub struct Readme {
+    /// Path in the tree, e.g. `docs/README.rst`.
+    pub path: String,
+    pub format: ReadmeFormat,
+    pub content: String,
+}
+
+impl Readme {
+    /// Prose of the README with the markup of its format removed.
+    pub fn text(&self) -> String {
+        match self.format {
+            ReadmeFormat::Markdown => Parser::new(&self.content)
+                .filter_map(|event| match event {
+                    Event::Text(t) | Event::Code(t) => Some(t.into_string()),
+                    Event::SoftBreak | Event::HardBreak | Event::End(_) => Some(String::from("\n")),
+                    _ => None,
+                })
+                .collect(),
+            ReadmeFormat::Rst | ReadmeFormat::Text => self
+                .content
+                .lines()
+                .filter(|l| !is_rst_adornment(l) && !l.trim_start().starts_with(".. "))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ReadmeFormat::AsciiDoc => self
+                .content
+                .lines()
+                .filter(|l| !is_adoc_delimiter(l) && !is_adoc_attribute(l))
+                .map(|l| adoc_heading(l).unwrap_or(l))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            ReadmeFormat::Org => self
+                .content
+                .lines()
+                .filter(|l| !l.trim_start().starts_with("#+"))
+                .map(|l| org_heading(l).unwrap_or(l))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+
+    pub fn headings(&self) -> usize {
+        match self.format {
+            ReadmeFormat::Markdown => count_headings(&self.content),
+            ReadmeFormat::Rst | ReadmeFormat::Text => {
+                let lines: Vec<&str> = self.content.lines().collect();
+                lines
+                    .windows(2)
+                    .filter(|w| {
+                        let title = w[0].trim();
+                        !title.is_empty()
+                            && !is_rst_adornment(w[0])
+                            && is_rst_adornment(w[1])
+                            && w[1].trim().chars().count() >= title.chars().count()
+                    })
+                    .count()
+            }
+            ReadmeFormat::AsciiDoc => {
+                let mut in_block = false;
+                self.content
+                    .lines()
+                    .filter(|l| {
+                        if is_adoc_delimiter(l) {
+                            in_block = !in_block;
+                        }
+                        !in_block && adoc_heading(l).is_some()
+                    })
+                    .count()
+            }
+            ReadmeFormat::Org => self.content.lines().filter(|l| org_heading(l).is_some()).count(),
+        }
+    }
+}
+
+/// reStructuredText section adornment: a line of one repeated punctuation character.
+fn is_rst_adornment(line: &str) -> bool {
+    let line = line.trim_end();
+    let mut chars = line.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    line.len() >= 3 && "=-~^\"'`#*+:._".contains(first) && chars.all(|c| c == first)
+}
+
+/// AsciiDoc listing, literal and example block delimiters.
+fn is_adoc_delimiter(line: &str) -> bool {
+    let line = line.trim_end();
+    line.len() >= 4 && (line.chars().all(|c| c == '-') || line.chars().all(|c| c == '.') || line.chars().all(|c| c == '='))
+}
+
+/// AsciiDoc `:name: value` attribute entry.
+fn is_adoc_attribute(line: &str) -> bool {
+    line.strip_prefix(':').is_some_and(|rest| rest.contains(':'))
+}
+
+/// Title of an AsciiDoc `== Section` line.
+fn adoc_heading(line: &str) -> Option<&str> {
+    let level = line.chars().take_while(|&c| c == '=').count();
+    let title = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then_some(title)
+}
+
+/// Title of an Org `** Section` line.
+fn org_heading(line: &str) -> Option<&str> {
+    let level = line.chars().take_while(|&c| c == '*').count();
+    let title = line[level..].strip_prefix(' ')?;
+    (level > 0).then_some(title)
+}
+
+/// README of the analysed tree, looked up case-insensitively in `.github/`,
+/// the root and `docs/`. Under `--range` only if the range touched it.
+pub fn get_readme(repo: &Repo) -> Result<Readme, Failure> {
+    let tree = repo.start_tree().ok_or_else(|| repo.no_commits())?;
+    let (path, format, entry) = README_DIRS
+        .iter()
+        .find_map(|dir| find_readme(repo, &tree, dir))
+        .ok_or_else(|| Failure::missing("no README in tree"))?;
+    if crate::code::touched_paths(repo).is_some_and(|t| !t.contains(&path)) {
+        return Err(Failure::missing(format!("{} not changed in range {}", path, repo.start_label())));
+    }
+    let blob = entry
+        .to_object(repo)
+        .and_then(|o| o.peel_to_blob())
+        .map_err(|e| AnalysisError::read(&path, e))?;
+    let content = String::from_utf8(blob.content().to_vec())
+        .map_err(|_| Failure::missing(format!("{} is not valid UTF-8", path)))?;
+    Ok(Readme { path, format, content })
+}
+
+fn find_readme(repo: &Repo, root: &git2::Tree, dir: &str) -> Option<(String, ReadmeFormat, git2::TreeEntry<'static>)> {
+    let subtree;
+    let tree = match dir {
+        "" => root,
+        dir => {
+            subtree = root.get_path(std::path::Path::new(dir)).ok()?.to_object(repo).ok()?.peel_to_tree().ok()?;
+            &subtree
+        }
+    };
+    tree.iter()
+        .filter(|e| e.kind() == Some(git2::ObjectType::Blob))
+        .filter_map(|e| {
+            let name = e.name()?.to_string();
+            let lower = name.to_lowercase();
+            let ext = lower.strip_prefix("readme")?;
+            let ext = match ext {
+                "" => "",
+                ext => ext.strip_prefix('.')?,
+            };
+            let rank = README_EXTENSIONS.iter().position(|(e, _)| *e == ext)?;
+            Some((rank, name, e.to_owned()))
+        })
+        .min_by_key(|(rank, _, _)| *rank)
+        .map(|(rank, name, entry)| {
+            let path = match dir {
+                "" => name,
+                dir => format!("{}/{}", dir, name),
+            };
+            (path, README_EXTENSIONS[rank].1, entry)
+        })
 }
 
 pub fn count_emojis(input: &str) -> usize {
@@ -30,8 +206,8 @@ pub fn count_emojis(input: &str) -> usize {
             .count()
 }
 
-pub fn count_headings_from_repo(repo: &Repo) -> Option<usize> {
-    get_readme(repo).map(|s| count_headings(&s))
+pub fn count_headings_from_repo(repo: &Repo) -> Result<usize, Failure> {
+    get_readme(repo).map(|r| r.headings())
 }
 pub fn count_headings(markdown: &str) -> usize {
     let parser = Parser::new(markdown);
@@ -42,55 +218,341 @@ pub fn count_headings(markdown: &str) -> usize {
     .count()
 }
 
-const LLM_INDICATORS: [&str;25] = [
-    "delve",
-    "tapestry",
-    "realm",
-    "testament",
-    "underscore",
-    "harness",
-    "leverage",
-    "meticulous",
-    "elevate",
-    "foster",
-    "unleash",
-    "comprehensive",
-    "transformative",
-    "demystify",
-    "paradigm",
-    "showcase",
-    "intricacies",
-    "pivotal",
-    "multifaceted",
-    "ever-evolving",
-    "landscape",
-    "dynamic",
-    "robust",
-    "spearhead",
-    "symphony",
+/// Typical LLM vocabulary: the lemma and every form that counts for it.
+/// Forms are listed explicitly so that e.g. "dynamic" doesn't pick up
+/// "dynamically".
+const LLM_INDICATORS: [(&str, &[&str]); 25] = [
+    ("delve", &["delve", "delves", "delved", "delving"]),
+    ("tapestry", &["tapestry", "tapestries"]),
+    ("realm", &["realm", "realms"]),
+    ("testament", &["testament", "testaments"]),
+    ("underscore", &["underscore", "underscores", "underscored", "underscoring"]),
+    ("harness", &["harness", "harnesses", "harnessed", "harnessing"]),
+    ("leverage", &["leverage", "leverages", "leveraged", "leveraging"]),
+    ("meticulous", &["meticulous", "meticulously"]),
+    ("elevate", &["elevate", "elevates", "elevated", "elevating"]),
+    ("foster", &["foster", "fosters", "fostered", "fostering"]),
+    ("unleash", &["unleash", "unleashes", "unleashed", "unleashing"]),
+    ("comprehensive", &["comprehensive"]),
+    ("transformative", &["transformative"]),
+    ("demystify", &["demystify", "demystifies", "demystified", "demystifying"]),
+    ("paradigm", &["paradigm", "paradigms"]),
+    ("showcase", &["showcase", "showcases", "showcased", "showcasing"]),
+    ("intricacies", &["intricacies", "intricacy"]),
+    ("pivotal", &["pivotal"]),
+    ("multifaceted", &["multifaceted", "multi-faceted"]),
+    ("ever-evolving", &["ever-evolving"]),
+    ("landscape", &["landscape", "landscapes"]),
+    ("dynamic", &["dynamic"]),
+    ("robust", &["robust"]),
+    ("spearhead", &["spearhead", "spearheads", "spearheaded", "spearheading"]),
+    ("symphony", &["symphony", "symphonies"]),
 ];
 
-pub fn get_llm_words(markdown: &str) -> HashMap<String,usize> {
-    let mut results: HashMap<String,usize> = HashMap::new();
-    for indicator in LLM_INDICATORS {
-        for _ in markdown.matches(indicator) {
-            let entry = results.entry(indicator.into()).or_default();
-            *entry += 1;
+/// Occurrence of an LLM indicator word in the README.
+pub struct WordMatch {
+    /// Lemma from [`LLM_INDICATORS`].
+    pub word: &'static str,
+    /// Form as written, case-folded.
+    pub form: String,
+    /// 1-based line number.
+    pub line: usize,
+    pub snippet: String,
+}
+
+/// LLM vocabulary in the README, case-insensitive and whole words only.
+/// Markdown code spans and code blocks are left out.
+pub fn get_llm_words(readme: &Readme) -> Vec<WordMatch> {
+    let source = readme.content.as_str();
+    let mut results = Vec::new();
+    if readme.format != ReadmeFormat::Markdown {
+        match_llm_words(source, 0..source.len(), &mut results);
+        return results;
+    }
+    let mut in_code_block = false;
+    for (event, range) in Parser::new(source).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Text(_) if !in_code_block => match_llm_words(source, range, &mut results),
+            _ => {}
         }
     }
     results
 }
-pub fn count_llm_words_repo(repo: &Repo) -> Option<HashMap<String, usize>> {
+
+fn match_llm_words(source: &str, range: std::ops::Range<usize>, results: &mut Vec<WordMatch>) {
+    let text = &source[range.clone()];
+    let words: Vec<(usize, &str)> = text.unicode_word_indices().collect();
+    let mut i = 0;
+    while i < words.len() {
+        let (start, word) = words[i];
+        // Hyphenated compounds are split by unicode_words, try them joined first
+        let compound = words.get(i + 1).and_then(|&(next, second)| {
+            (&text[start + word.len()..next] == "-").then(|| format!("{}-{}", word, second))
+        });
+        let (form, consumed) = match compound.map(|c| c.to_lowercase()) {
+            Some(c) if lemma_of(&c).is_some() => (c, 2),
+            _ => (word.to_lowercase(), 1),
+        };
+        if let Some(lemma) = lemma_of(&form) {
+            let (line, _, snippet) = locate(source, range.start + start);
+            results.push(WordMatch {
+                word: lemma,
+                form,
+                line,
+                snippet,
+            });
+        }
+        i += consumed;
+    }
+}
+
+fn lemma_of(form: &str) -> Option<&'static str> {
+    LLM_INDICATORS
+        .iter()
+        .find(|(_, forms)| forms.contains(&form))
+        .map(|(lemma, _)| *lemma)
+}
+
+/// Phrases typical for LLM prose as (label, pattern, weight). Patterns are
+/// case-insensitive regexes, `'` also matches a typographic apostrophe.
+const LLM_PHRASES: [(&str, &str, f64); 18] = [
+    ("whether you're a beginner or", r"\bwhether you're an? (beginner|novice|newcomer|seasoned|experienced)\b", 2.0),
+    ("in today's fast-paced", r"\bin today's (fast-paced|digital|ever-changing|rapidly evolving)\b", 2.0),
+    ("look no further", r"\blook no further\b", 2.0),
+    ("in the ever-evolving world of", r"\bin the (ever-evolving|ever-changing|rapidly evolving) (world|landscape|realm) of\b", 2.0),
+    ("it's worth noting", r"\bit(?:'s| is) worth noting\b", 1.5),
+    ("seamlessly integrates", r"\bseamless(ly)? integrat(es|ed|ing|ion|e)\b", 1.5),
+    ("a testament to", r"\ba testament to\b", 1.5),
+    ("not just X, but Y", r"\bnot (just|only|merely) [^.,;:\n]{1,40}, but\b", 1.5),
+    ("to the next level", r"\bto the next level\b", 1.5),
+    ("unlock the full potential", r"\bunlock(s|ing)? the (full )?(power|potential)\b", 1.5),
+    ("plays a crucial role", r"\bplays? an? (crucial|pivotal|vital|key) role\b", 1.5),
+    ("navigate the complexities", r"\bnavigat(e|ing) the (complexities|intricacies)\b", 1.5),
+    ("dive into", r"\b(let's )?dive (deep(er)? )?into\b", 1.0),
+    ("designed with X in mind", r"\bdesigned with [^.\n]{1,30} in mind\b", 1.0),
+    ("empowers you to", r"\bempower(s|ing)? (developers|you|users|teams) to\b", 1.0),
+    ("a wide range of", r"\ba (wide|diverse|rich|broad) (range|array|variety|spectrum) of\b", 1.0),
+    ("streamline your workflow", r"\bstreamlin(e|es|ing) your (workflow|development|process)\b", 1.0),
+    ("game-changer", r"\bgame[- ]chang(er|ing)\b", 1.0),
+];
+
+/// Occurrence of an [`LLM_PHRASES`] entry in the README.
+pub struct PhraseMatch {
+    pub phrase: &'static str,
+    pub weight: f64,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, in characters.
+    pub column: usize,
+    /// Byte offset in the README.
+    pub offset: usize,
+    pub snippet: String,
+}
+
+/// README prose with the source offset of each piece, so matches in the
+/// prose can be traced back to lines of the file.
+struct Prose {
+    text: String,
+    /// Offset and length in `text` of each piece, with the source range of
+    /// the text event it came from.
+    pieces: Vec<(usize, usize, Range<usize>)>,
+}
+
+impl Prose {
+    /// Markdown text outside code, with block boundaries as line breaks.
+    /// Other formats are taken as they are.
+    fn of(readme: &Readme) -> Self {
+        let source = readme.content.as_str();
+        if readme.format != ReadmeFormat::Markdown {
+            return Self { text: source.to_string(), pieces: vec![(0, source.len(), 0..source.len())] };
+        }
+        let mut prose = Self { text: String::new(), pieces: Vec::new() };
+        let mut in_code_block = false;
+        for (event, range) in Parser::new(source).into_offset_iter() {
+            match event {
+                Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+                Event::End(TagEnd::CodeBlock) => in_code_block = false,
+                Event::Text(t) if !in_code_block => {
+                    prose.pieces.push((prose.text.len(), t.len(), range));
+                    prose.text.push_str(&t);
+                }
+                Event::Code(_) | Event::SoftBreak | Event::HardBreak => prose.text.push(' '),
+                Event::End(TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::Item | TagEnd::TableCell) => {
+                    prose.text.push('\n')
+                }
+                _ => {}
+            }
+        }
+        prose
+    }
+
+    /// Source offset of `offset` in the prose. Inside a piece rendered
+    /// differently from its source (entities, escapes) that is the start of
+    /// the piece's source range.
+    fn source_offset(&self, offset: usize) -> usize {
+        let i = self.pieces.partition_point(|(start, _, _)| *start <= offset);
+        let Some((start, len, source)) = i.checked_sub(1).map(|i| &self.pieces[i]) else {
+            return 0;
+        };
+        match *len == source.len() && offset - start < *len {
+            true => source.start + (offset - start),
+            false => source.start,
+        }
+    }
+}
+
+pub fn get_llm_phrases(readme: &Readme) -> Vec<PhraseMatch> {
+    static PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
+        LLM_PHRASES
+            .iter()
+            .map(|(_, pattern, _)| Regex::new(&format!("(?i){}", pattern.replace('\'', "['’]"))).unwrap())
+            .collect()
+    });
+    let prose = Prose::of(readme);
+    let mut results: Vec<PhraseMatch> = Vec::new();
+    for ((phrase, _, weight), pattern) in LLM_PHRASES.iter().zip(PATTERNS.iter()) {
+        for m in pattern.find_iter(&prose.text) {
+            let offset = char_boundary(&readme.content, prose.source_offset(m.start()));
+            let (line, column, snippet) = locate(&readme.content, offset);
+            results.push(PhraseMatch { phrase, weight: *weight, line, column, offset, snippet });
+        }
+    }
+    results.sort_by_key(|m| m.offset);
+    results
+}
+
+pub fn count_llm_phrases_repo(repo: &Repo) -> Result<(String, Vec<PhraseMatch>), Failure> {
+    let readme = get_readme(repo)?;
+    let matches = get_llm_phrases(&readme);
+    Ok((readme.path, matches))
+}
+
+/// 1-based line and column of `offset`, with a snippet of the line around it.
+fn locate(source: &str, offset: usize) -> (usize, usize, String) {
+    let offset = char_boundary(source, offset);
+    let line_start = source[..offset].rfind('\n').map_or(0, |p| p + 1);
+    let line_end = source[offset..].find('\n').map_or(source.len(), |p| offset + p);
+    let line = &source[line_start..line_end];
+    let column = source[line_start..offset].chars().count();
+    (source[..offset].matches('\n').count() + 1, column + 1, snippet(line, column))
+}
+
+/// `offset` moved back to the start of the char it falls into.
+fn char_boundary(source: &str, offset: usize) -> usize {
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+/// Part of `line` around char `column`, shortened for evidence output.
+fn snippet(line: &str, column: usize) -> String {
+    const MAX_CHARS: usize = 80;
+    const BEFORE: usize = 30;
+    let chars: Vec<char> = line.chars().collect();
+    let start = match chars.len() > MAX_CHARS {
+        true => column.saturating_sub(BEFORE).min(chars.len() - MAX_CHARS),
+        false => 0,
+    };
+    let end = (start + MAX_CHARS).min(chars.len());
+    let mut snippet: String = chars[start..end].iter().collect::<String>().trim().to_string();
+    if start > 0 {
+        snippet.insert_str(0, "...");
+    }
+    if end < chars.len() {
+        snippet.push_str("...");
+    }
+    snippet
+}
+pub fn count_llm_words_repo(repo: &Repo) -> Result<(String, Vec<WordMatch>), Failure> {
     let readme = crate::readme::get_readme(repo)?;
-    Some(crate::readme::get_llm_words(&readme))
+    let matches = crate::readme::get_llm_words(&readme);
+    Ok((readme.path, matches))
 }
 
-pub fn length_in_words(repo: &Repo) -> Option<usize> {
+pub fn length_in_words(repo: &Repo) -> Result<usize, Failure> {
     let readme = crate::readme::get_readme(repo)?;
     let mut counter = 0;
-    for _ in readme.unicode_words() {
+    for _ in readme.text().unicode_words() {
         counter += 1
     }
-    Some(counter)
+    Ok(counter)
 }
 
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn readme(format: ReadmeFormat, content: &str) -> Readme {
+        Readme { path: String::new(), format, content: content.to_string() }
+    }
+
+    #[test]
+    fn test_llm_words_have_line_numbers() {
+        let matches = get_llm_words(&readme(ReadmeFormat::Markdown, "# Title\n\nA robust tool.\nIt will delve into a robust paradigm.\n"));
+        let found: Vec<(&str, usize)> = matches.iter().map(|m| (m.word, m.line)).collect();
+        assert_eq!(found, vec![("robust", 3), ("delve", 4), ("robust", 4), ("paradigm", 4)]);
+        assert_eq!(matches[1].snippet, "It will delve into a robust paradigm.");
+    }
+
+    #[test]
+    fn test_llm_words_are_whole_words_outside_code() {
+        let text = "Delving into realms, we Leveraged an ever-evolving stack.\n\
+            It scales dynamically and is unharnessed.\n\
+            Use `robust` here.\n\n```\nlet robust = true;\n```\n";
+        let matches = get_llm_words(&readme(ReadmeFormat::Markdown, text));
+        let found: Vec<(&str, &str)> = matches.iter().map(|m| (m.word, m.form.as_str())).collect();
+        assert_eq!(found, vec![
+            ("delve", "delving"),
+            ("realm", "realms"),
+            ("leverage", "leveraged"),
+            ("ever-evolving", "ever-evolving"),
+        ]);
+    }
+
+    #[test]
+    fn test_llm_phrases_with_positions() {
+        let text = "# Tool\n\nWhether you're a beginner or an expert,\nit’s worth noting that this is **not just fast**, but small.\n\n```\na testament to\n```\n";
+        let matches = get_llm_phrases(&readme(ReadmeFormat::Markdown, text));
+        let found: Vec<(&str, usize)> = matches.iter().map(|m| (m.phrase, m.line)).collect();
+        assert_eq!(found, vec![
+            ("whether you're a beginner or", 3),
+            ("it's worth noting", 4),
+            ("not just X, but Y", 4),
+        ]);
+        assert_eq!(matches.iter().map(|m| m.weight).sum::<f64>(), 5.0);
+    }
+
+    #[test]
+    fn test_llm_phrases_after_entities() {
+        let text = "Caf&eacute; &amp; cr\u{e8}me \\* br\u{fb}l\u{e9}e: whether you're a beginner or not.\n";
+        let matches = get_llm_phrases(&readme(ReadmeFormat::Markdown, text));
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].line, 1);
+        assert!(text.is_char_boundary(matches[0].offset));
+        assert_eq!(locate("cr\u{e8}me", 3).1, 3);
+    }
+
+    #[test]
+    fn test_headings_and_text_per_format() {
+        let rst = readme(ReadmeFormat::Rst, "=====\nTitle\n=====\n\nIntro text.\n\nUsage\n-----\n\n.. note:: hidden\n");
+        assert_eq!(rst.headings(), 2);
+        assert_eq!(rst.text().unicode_words().count(), 4);
+
+        let adoc = readme(ReadmeFormat::AsciiDoc, "= Title\n:toc:\n\n== Usage\n\n----\n= not a heading\n----\n");
+        assert_eq!(adoc.headings(), 2);
+        assert_eq!(adoc.text().unicode_words().count(), 5);
+
+        let org = readme(ReadmeFormat::Org, "#+TITLE: Tool\n* Intro\nSome text\n** Details\n*bold* line\n");
+        assert_eq!(org.headings(), 2);
+        assert_eq!(org.text().unicode_words().count(), 6);
+
+        let md = readme(ReadmeFormat::Markdown, "# Title\n\nSee [the docs](https://example.com/a/b).\n");
+        assert_eq!(md.headings(), 1);
+        assert_eq!(md.text().unicode_words().count(), 4);
+    }
+}
@@ -1,55 +1,327 @@
-use std::{path::PathBuf, sync::Once};
+use std::{collections::HashMap, fmt::Display, path::{Path, PathBuf}, sync::{Arc, Mutex}};
 
-use anyhow::Context;
-use git2::{FetchOptions, Repository};
+use git2::{FetchOptions, Oid, Repository};
+use once_cell::sync::Lazy;
 
-use crate::{staging_dir::StagingDir, traits::Repo};
+use crate::{error::{AnalysisError, Failure}, options::{Options, DEFAULT_CACHE_TTL}, staging_dir::StagingDir, traits::Repo};
 
-static REPO_MSG: Once = Once::new();
+/// libgit2's `GIT_FETCH_DEPTH_UNSHALLOW`.
+const UNSHALLOW_DEPTH: i32 = i32::MAX;
+/// Key in the cached clone's git config holding the last fetch time (unix seconds).
+const FETCHED_KEY: &str = "vibe-coded.fetched";
+/// Key in the cached clone's git config holding the URL it was cloned from,
+/// slugs are not reversible.
+const URL_KEY: &str = "vibe-coded.url";
+/// Environment variable overriding the cache root.
+pub const CACHE_DIR_ENV: &str = "VIBE_CODED_CACHE";
 
-pub fn path_from_url(url: &str) -> PathBuf {
-    let slug = slug_from_url(url);
-    let tmpdir_root = std::env::temp_dir();
-    let dest_path = &tmpdir_root.join("vibe-coded").join(slug);
-    dest_path.clone()
+/// One lock per cache directory, so equivalent URLs analysed at the same
+/// time (e.g. in `--batch`) don't clone or refresh the same clone at once.
+static CLONE_LOCKS: Lazy<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> = Lazy::new(Default::default);
+
+/// Repository to analyse: either a remote URL that gets cloned into the
+/// cache, or a working copy (or bare repository) already on disk.
+#[derive(Clone, Debug)]
+pub enum Target {
+    Remote(String),
+    Local(PathBuf),
+}
+
+impl Target {
+    /// Existing paths are treated as local repositories, everything else as URL.
+    pub fn parse(target: &str) -> Self {
+        if Path::new(target).exists() {
+            Target::Local(PathBuf::from(target))
+        } else {
+            Target::Remote(String::from(target))
+        }
+    }
+}
+
+impl Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Target::Remote(url) => f.write_str(url),
+            Target::Local(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+pub fn open_repository(target: &Target, options: &Options) -> Result<Repository, AnalysisError> {
+    match target {
+        Target::Remote(url) => {
+            let repo = clone_repository(url, options)?;
+            let range_ends = options.range_ends().map(|(base, tip)| [base, tip]);
+            let refs = options.git_ref.as_deref().into_iter().chain(range_ends.into_iter().flatten());
+            for git_ref in refs {
+                if resolve_ref(&repo, git_ref).is_err() {
+                    fetch_ref(&repo, git_ref, options)?;
+                }
+            }
+            Ok(repo)
+        }
+        Target::Local(path) => {
+            let repo = Repository::discover(path)
+                .map_err(|e| AnalysisError::read(format!("repository at {}", path.display()), e))?;
+            eprintln!("Using local repository at: {}", repo.path().to_string_lossy());
+            Ok(repo)
+        }
+    }
+}
+
+/// Directory holding cached clones: `--cache-dir`, `$VIBE_CODED_CACHE`, or
+/// [`default_cache_root`].
+pub fn cache_root(options: &Options) -> PathBuf {
+    options
+        .cache_dir
+        .clone()
+        .or_else(|| std::env::var_os(CACHE_DIR_ENV).map(PathBuf::from))
+        .unwrap_or_else(default_cache_root)
+}
+
+/// `vibe-coded` in the temp dir.
+pub fn default_cache_root() -> PathBuf {
+    std::env::temp_dir().join("vibe-coded")
+}
+
+pub fn path_from_url(url: &str, options: &Options) -> PathBuf {
+    cache_root(options).join(slug_from_url(url))
 }
-pub fn clone_repository(url: &str) -> Result<Repository, anyhow::Error> {
-    let dest_path = &path_from_url(url);
+pub fn clone_repository(url: &str, options: &Options) -> Result<Repository, AnalysisError> {
+    let dest_path = &path_from_url(url, options);
+    let lock = Arc::clone(CLONE_LOCKS.lock().unwrap().entry(dest_path.clone()).or_default());
+    let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
     if dest_path.exists() {
-        REPO_MSG.call_once(|| {
-            println!("Repository exists at: {}", &dest_path.to_string_lossy());
-        });
-        return git2::Repository::open(dest_path).context("Can't existing open repository");
+        eprintln!("Repository exists at: {}", &dest_path.to_string_lossy());
+        let repo = git2::Repository::open(dest_path)
+            .map_err(|e| AnalysisError::read(format!("cached clone at {}", dest_path.display()), e))?;
+        let deepen = repo.is_shallow() && (options.full_history || options.depth.is_some());
+        // An unreachable origin shouldn't stop the analysis, the clone is
+        // still usable and stays stale so the next run tries again.
+        if (deepen || options.refresh || is_stale(&repo, options))
+            && let Err(e) = refresh(&repo, options)
+        {
+            eprintln!("Warning: {}, analysing the cached clone as is", e);
+        }
+        return Ok(repo);
     };
-    let mut destination = StagingDir::try_new(dest_path)?;
+    let mut destination = StagingDir::try_new(dest_path).map_err(|e| AnalysisError::Io {
+        path: dest_path.display().to_string(),
+        reason: e.to_string(),
+    })?;
     let mut fo = FetchOptions::new();
-    fo.depth(100);
+    fo.depth(options.clone_depth());
     let mut builder = git2::build::RepoBuilder::new();
     builder.fetch_options(fo);
 
-	 	println!("Fetching repository, it might take a while");
-    let repo = builder.clone(url, &destination.path)?;
+	 	eprintln!("Fetching {}, it might take a while", url);
+    let clone_failed = |e: git2::Error| AnalysisError::CloneFailed {
+        url: url.to_string(),
+        reason: e.message().to_string(),
+    };
+    let repo = builder.clone(url, &destination.path).map_err(clone_failed)?;
+    repo.config().and_then(|mut c| c.set_str(URL_KEY, url)).map_err(clone_failed)?;
+    record_fetch(&repo).map_err(clone_failed)?;
     destination.persist();
-    REPO_MSG.call_once(|| {
-        println!("Repository created at: {}", &dest_path.to_string_lossy());
-    });
+    eprintln!("Repository created at: {}", &dest_path.to_string_lossy());
 
     Ok(repo)
 }
 
+/// Resolves a branch, tag or commit id to a commit, falling back to the
+/// remote-tracking branch of `origin` (clones only have the default branch
+/// locally).
+pub fn resolve_ref(repo: &Repository, git_ref: &str) -> Result<Oid, AnalysisError> {
+    [git_ref.to_string(), format!("origin/{}", git_ref)]
+        .iter()
+        .find_map(|r| repo.revparse_single(r).ok()?.peel_to_commit().ok())
+        .map(|c| c.id())
+        .ok_or_else(|| AnalysisError::RefNotFound(git_ref.to_string()))
+}
+
+/// Fetches `git_ref` from `origin`, trying it as a branch, a tag and finally
+/// as a commit id.
+fn fetch_ref(repo: &Repository, git_ref: &str, options: &Options) -> Result<(), AnalysisError> {
+    let mut remote = repo.find_remote("origin").map_err(|_| AnalysisError::FetchFailed {
+        what: format!("ref '{}'", git_ref),
+        reason: String::from("repository has no origin remote"),
+    })?;
+    let refspecs = [
+        format!("+refs/heads/{0}:refs/remotes/origin/{0}", git_ref),
+        format!("+refs/tags/{0}:refs/tags/{0}", git_ref),
+        git_ref.to_string(),
+    ];
+    eprintln!("Fetching {}, it might take a while", git_ref);
+    for refspec in refspecs {
+        let mut fo = FetchOptions::new();
+        fo.depth(options.clone_depth());
+        if remote.fetch(&[&refspec], Some(&mut fo), None).is_ok() && resolve_ref(repo, git_ref).is_ok() {
+            return Ok(());
+        }
+    }
+    Err(AnalysisError::RefNotFound(git_ref.to_string()))
+}
+
+/// Unix time of the last fetch into a cached clone, if recorded.
+pub fn last_fetched(repo: &Repository) -> Option<i64> {
+    repo.config().ok()?.get_i64(FETCHED_KEY).ok()
+}
+
+/// URL a cached clone was made from, `None` for repositories not cloned by
+/// vibe-coded.
+pub fn original_url(repo: &Repository) -> Option<String> {
+    repo.config().ok()?.get_string(URL_KEY).ok()
+}
+
+fn record_fetch(repo: &Repository) -> Result<(), git2::Error> {
+    repo.config()?.set_i64(FETCHED_KEY, now())
+}
+
+fn is_stale(repo: &Repository, options: &Options) -> bool {
+    let ttl = options.cache_ttl.unwrap_or(DEFAULT_CACHE_TTL).as_secs() as i64;
+    last_fetched(repo).is_none_or(|fetched| now() - fetched > ttl)
+}
+
+pub(crate) fn now() -> i64 {
+    std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs() as i64
+}
+
+/// Fetches `origin` into a cached clone and fast-forwards the checked out
+/// branch. Shallow clones are deepened to the requested depth, or
+/// unshallowed for `--full-history`; complete clones stay complete.
+fn refresh(repo: &Repository, options: &Options) -> Result<(), AnalysisError> {
+    let fetch_failed = |what: &str, e: git2::Error| AnalysisError::FetchFailed {
+        what: what.to_string(),
+        reason: e.message().to_string(),
+    };
+    let mut remote = repo.find_remote("origin").map_err(|e| fetch_failed("origin", e))?;
+    let what = remote.url().unwrap_or("origin").to_string();
+    let mut fo = FetchOptions::new();
+    fo.depth(match (repo.is_shallow(), options.full_history) {
+        (false, _) => 0,
+        (true, true) => UNSHALLOW_DEPTH,
+        (true, false) => options.clone_depth(),
+    });
+    eprintln!("Refreshing cached clone, it might take a while");
+    remote.fetch::<&str>(&[], Some(&mut fo), None).map_err(|e| fetch_failed(&what, e))?;
+    fast_forward(repo).and_then(|()| record_fetch(repo)).map_err(|e| fetch_failed(&what, e))
+}
+
+/// Moves the checked out branch to its upstream after a fetch.
+fn fast_forward(repo: &Repository) -> Result<(), git2::Error> {
+    let head = repo.head()?;
+    if !head.is_branch() {
+        return Ok(());
+    }
+    let branch = git2::Branch::wrap(head);
+    let Some(upstream) = branch.upstream().ok().and_then(|u| u.get().target()) else {
+        return Ok(());
+    };
+    let head = branch.into_reference();
+    if head.target() == Some(upstream) {
+        return Ok(());
+    }
+    let name = head.name().ok_or_else(|| git2::Error::from_str("branch name is not UTF-8"))?;
+    repo.reference(name, upstream, true, "vibe-coded: fast-forward")?;
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))?;
+    Ok(())
+}
+
+/// Cache directory name for `url`: a readable prefix plus a SHA-256 prefix
+/// of the normalized URL, so equivalent URLs share one clone and slugs stay
+/// the same across toolchains.
 fn slug_from_url(url: &str) -> String {
-    let sanitized: String = url
+    use sha2::{Digest, Sha256};
+
+    let normalized = normalize_url(url);
+    let readable: String = normalized
         .chars()
         .map(|c| if c.is_alphanumeric() { c } else { '-' })
-        .take(30)
+        .take(40)
         .collect();
+    let hash: String = Sha256::digest(normalized.as_bytes())[..8]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+    format!("{}-{}", readable.trim_matches('-'), hash)
+}
+
+/// Reduces the usual spellings of a remote to `host/path`: scheme, user,
+/// scp-style `host:path`, a trailing `/` and `.git` are dropped and the host
+/// is lowercased.
+fn normalize_url(url: &str) -> String {
+    let url = url.trim();
+    let (rest, scp) = match url.split_once("://") {
+        Some((_, rest)) => (rest, false),
+        None => (url, url.contains(':')),
+    };
+    let rest = match rest.split_once('@') {
+        Some((user, rest)) if !user.contains('/') => rest,
+        _ => rest,
+    };
+    let (host, path) = match scp {
+        true => rest.split_once(':').unwrap_or((rest, "")),
+        false => rest.split_once('/').unwrap_or((rest, "")),
+    };
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path).trim_end_matches('/');
+    match path.is_empty() {
+        true => host.to_lowercase(),
+        false => format!("{}/{}", host.to_lowercase(), path),
+    }
+}
 
-    let mut hasher = std::collections::hash_map::DefaultHasher::new();
-    std::hash::Hash::hash(url, &mut hasher);
-    use std::hash::Hasher;
-    format!("{}-{:x}", sanitized.trim_matches('-'), hasher.finish())
+pub fn llm_specific_files(repo: &Repo) -> Result<Vec<String>, Failure> {
+    Ok(crate::code::get_repowalk_data(repo)?.files.clone())
 }
 
-pub fn llm_specific_files(repo: &Repo) -> Option<Vec<String>> {
-    crate::code::get_repowalk_data(repo).map(|d| d.files)
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::staging_dir::StagingDir;
+
+    #[test]
+    fn test_failed_refresh_keeps_cached_clone() {
+        let root = std::env::temp_dir().join(format!("vibe-coded-test-refresh-{}", std::process::id()));
+        let dir = StagingDir::try_new(&root).unwrap();
+        let origin_path = dir.path.join("origin");
+        let origin = Repository::init(&origin_path).unwrap();
+        std::fs::write(origin_path.join("file"), "content\n").unwrap();
+        let mut index = origin.index().unwrap();
+        index.add_path(Path::new("file")).unwrap();
+        let tree = origin.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let head = origin.commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[]).unwrap();
+
+        // The local transport can't do shallow fetches.
+        let options = Options { cache_dir: Some(dir.path.join("cache")), full_history: true, ..Default::default() };
+        let url = format!("file://{}", origin_path.display());
+        let clone = clone_repository(&url, &options).unwrap();
+        clone.config().unwrap().set_i64(FETCHED_KEY, 0).unwrap();
+        std::fs::remove_dir_all(&origin_path).unwrap();
+
+        let clone = clone_repository(&url, &options).unwrap();
+        assert_eq!(clone.head().unwrap().target(), Some(head));
+        assert_eq!(last_fetched(&clone), Some(0));
+
+        let no_origin = Repository::init(dir.path.join("no-origin")).unwrap();
+        let error = fetch_ref(&no_origin, "main", &options).unwrap_err();
+        assert_eq!(error.to_string(), "can't fetch ref 'main': repository has no origin remote");
+    }
+
+    #[test]
+    fn test_equivalent_urls_share_a_slug() {
+        let slug = slug_from_url("https://github.com/a/b");
+        for url in [
+            "https://github.com/a/b/",
+            "https://github.com/a/b.git",
+            "https://GitHub.com/a/b.git/",
+            "git@github.com:a/b.git",
+            "ssh://git@github.com/a/b",
+        ] {
+            assert_eq!(slug_from_url(url), slug, "{}", url);
+        }
+        assert_eq!(slug, "github-com-a-b-b703f2afd8642d79");
+        assert_ne!(slug_from_url("https://github.com/a/c"), slug);
+    }
 }
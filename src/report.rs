@@ -0,0 +1,322 @@
+use std::{collections::{HashMap, HashSet}, str::FromStr};
+
+use anyhow::anyhow;
+use serde::Serialize;
+
+use crate::{authors::AuthorStats, options::Options, rule_formatter::{ResultType, RuleFormatter}, traits::{RuleResult, Vibe}};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            other => Err(anyhow!("Unknown format '{}', expected 'text' or 'json'", other)),
+        }
+    }
+}
+
+/// Structured outcome of a single rule, as emitted in the JSON report.
+#[derive(Serialize, Debug)]
+pub struct RuleReport {
+    pub id: &'static str,
+    pub verdict: Vibe,
+    pub value: Option<f64>,
+    pub threshold: String,
+    pub message: Option<String>,
+    /// Filled for rules selected by `--explain`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub evidence: Vec<String>,
+}
+
+/// Rule that failed to produce a verdict.
+#[derive(Serialize, Debug)]
+pub struct FailedReport {
+    pub id: &'static str,
+    pub reason: String,
+}
+
+pub struct Report {
+    pub target: String,
+    pub git_ref: Option<String>,
+    pub range: Option<String>,
+    /// Rules that ignore `range` and still reflect the whole tree.
+    pub whole_tree: Vec<&'static str>,
+    pub head: Option<String>,
+    /// Analysed from a shallow clone, history-based rules may be lower bounds.
+    pub shallow: bool,
+    /// When the cached clone was last fetched (unix seconds), for remote targets.
+    pub fetched: Option<i64>,
+    /// Rules whose evidence is included, from `--explain`.
+    pub explain: HashSet<&'static str>,
+    /// Per-author statistics, filled with `--authors`.
+    pub authors: Vec<AuthorStats>,
+    pub(crate) results: Vec<Box<dyn RuleResult>>,
+    pub(crate) weights: HashMap<&'static str, f64>,
+}
+
+#[derive(Serialize)]
+pub(crate) struct JsonReport<'a> {
+    target: &'a str,
+    #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+    git_ref: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    range: Option<&'a str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    whole_tree: Vec<&'static str>,
+    head: Option<&'a str>,
+    shallow: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fetched: Option<i64>,
+    score: Option<usize>,
+    rules: Vec<RuleReport>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    authors: &'a [AuthorStats],
+    failed: Vec<FailedReport>,
+}
+
+impl Report {
+    pub fn rules(&self) -> Vec<RuleReport> {
+        self.results
+            .iter()
+            .filter(|r| r.failure().is_none())
+            .map(|r| {
+                let mut report = r.report();
+                if self.explain.contains(r.name()) {
+                    report.evidence = r.evidence();
+                }
+                report
+            })
+            .collect()
+    }
+
+    pub fn failed(&self) -> Vec<FailedReport> {
+        self.results
+            .iter()
+            .filter_map(|r| {
+                r.failure().map(|reason| FailedReport {
+                    id: r.name(),
+                    reason,
+                })
+            })
+            .collect()
+    }
+
+    /// Weighted share of decided rules that came out as VIBE, in percent.
+    ///
+    /// Undecided and failed rules are left out of the denominator, so `None`
+    /// means no rule reached a verdict.
+    pub fn score(&self) -> Option<usize> {
+        let mut vibe = 0.0;
+        let mut total = 0.0;
+        for r in &self.results {
+            if r.failure().is_some() {
+                continue;
+            }
+            let weight = self.weights.get(r.name()).copied().unwrap_or(1.0);
+            match r.is_vibe() {
+                Vibe::Yes => {
+                    vibe += weight;
+                    total += weight;
+                }
+                Vibe::No => total += weight,
+                Vibe::Undecided => {}
+            }
+        }
+        if total <= 0.0 {
+            return None;
+        }
+        Some((vibe / total * 100.0).round() as usize)
+    }
+
+    /// Whether at least one rule produced a result instead of failing.
+    pub fn has_results(&self) -> bool {
+        self.results.iter().any(|r| r.failure().is_none())
+    }
+
+    /// `--fail-on` rules that can't trip the gate because they failed or
+    /// did not run.
+    pub fn unchecked_gates(&self, options: &Options) -> Vec<String> {
+        options
+            .fail_on
+            .iter()
+            .filter_map(|id| match self.results.iter().find(|r| r.name() == id) {
+                None => Some(format!("--fail-on {} has no verdict, the rule is disabled or not selected", id)),
+                Some(r) => r.failure().map(|reason| format!("--fail-on {} has no verdict, the rule failed: {}", id, reason)),
+            })
+            .collect()
+    }
+
+    /// Whether the report crosses the gates configured in `options`.
+    pub fn exceeds(&self, options: &Options) -> bool {
+        let flagged_rule = self.results.iter().any(|r| {
+            options.fail_on.iter().any(|id| id == r.name()) && r.is_vibe() == Vibe::Yes
+        });
+        let above = match (options.fail_above, self.score()) {
+            (Some(limit), Some(score)) => score > limit,
+            _ => false,
+        };
+        flagged_rule || above
+    }
+
+    pub(crate) fn json(&self) -> JsonReport<'_> {
+        JsonReport {
+            target: &self.target,
+            git_ref: self.git_ref.as_deref(),
+            range: self.range.as_deref(),
+            whole_tree: self.whole_tree.clone(),
+            head: self.head.as_deref(),
+            shallow: self.shallow,
+            fetched: self.fetched,
+            score: self.score(),
+            rules: self.rules(),
+            authors: &self.authors,
+            failed: self.failed(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, anyhow::Error> {
+        Ok(serde_json::to_string_pretty(&self.json())?)
+    }
+
+    pub fn render(&self) {
+        for r in &self.results {
+            r.render();
+            if self.explain.contains(r.name()) {
+                for line in r.evidence() {
+                    println!("{:11}{}", "", line);
+                }
+            }
+        }
+        if !self.authors.is_empty() {
+            println!();
+            for line in crate::authors::table(&self.authors) {
+                println!("{:11}{}", "", line);
+            }
+            println!();
+        }
+        if let Some(fetched) = self.fetched {
+            let age = crate::cache::format_age((crate::repo::now() - fetched).max(0));
+            RuleFormatter {
+                rule_name: "cache",
+                msg: Some(format!("Clone last fetched {} ago", age)),
+                result_type: ResultType::Info,
+                ..Default::default()
+            }.print();
+        }
+        if let Some(range) = &self.range {
+            let whole_tree = match self.whole_tree.is_empty() {
+                true => String::from("all rules are range-aware"),
+                false => format!("whole tree: {}", self.whole_tree.join(", ")),
+            };
+            RuleFormatter {
+                rule_name: "range",
+                msg: Some(format!("{} ({})", range, whole_tree)),
+                result_type: ResultType::Info,
+                ..Default::default()
+            }.print();
+        }
+        match self.score() {
+            Some(score) => RuleFormatter {
+                rule_name: "overall",
+                result_type: ResultType::Percentage(score),
+                ..Default::default()
+            },
+            None => RuleFormatter {
+                rule_name: "overall",
+                msg: Some(String::from("no rule reached a verdict")),
+                result_type: ResultType::Info,
+                ..Default::default()
+            },
+        }.print();
+    }
+
+    pub fn print(&self, format: Format) -> Result<(), anyhow::Error> {
+        match format {
+            Format::Text => self.render(),
+            Format::Json => println!("{}", self.to_json()?),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::failed_result::FailedResult;
+
+    struct Fixed(&'static str, Vibe);
+    impl RuleResult for Fixed {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+        fn is_vibe(&self) -> Vibe {
+            self.1
+        }
+    }
+
+    fn report_of(results: Vec<Box<dyn RuleResult>>, weights: &[(&'static str, f64)]) -> Report {
+        Report {
+            target: String::new(),
+            git_ref: None,
+            range: None,
+            whole_tree: Vec::new(),
+            head: None,
+            shallow: false,
+            fetched: None,
+            explain: HashSet::new(),
+            authors: Vec::new(),
+            results,
+            weights: weights.iter().copied().collect(),
+        }
+    }
+
+    #[test]
+    fn test_score_is_weighted_and_skips_undecided() {
+        let report = report_of(
+            vec![
+                Box::new(Fixed("a", Vibe::Yes)),
+                Box::new(Fixed("b", Vibe::No)),
+                Box::new(Fixed("c", Vibe::Undecided)),
+                Box::new(FailedResult::from("d")),
+            ],
+            &[("a", 3.0), ("b", 1.0), ("c", 5.0), ("d", 5.0)],
+        );
+        assert_eq!(report.score(), Some(75));
+    }
+
+    #[test]
+    fn test_gates() {
+        let report = report_of(
+            vec![Box::new(Fixed("a", Vibe::Yes)), Box::new(Fixed("b", Vibe::No)), Box::new(FailedResult::from("c"))],
+            &[],
+        );
+        let gate = |fail_above, fail_on: &[&str]| Options {
+            fail_above,
+            fail_on: fail_on.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        };
+        // Opt-in: no gate, no failure even though a rule says VIBE
+        assert!(!report.exceeds(&gate(None, &[])));
+        assert!(report.exceeds(&gate(Some(40), &[])));
+        assert!(!report.exceeds(&gate(Some(50), &[])));
+        assert!(report.exceeds(&gate(None, &["a"])));
+        assert!(!report.exceeds(&gate(None, &["b", "c"])));
+        let unchecked = report.unchecked_gates(&gate(None, &["a", "c", "d"]));
+        assert_eq!(unchecked.len(), 2);
+        assert!(unchecked[0].starts_with("--fail-on c has no verdict, the rule failed"));
+        assert!(unchecked[1].contains("disabled or not selected"));
+
+        assert!(report.has_results());
+        let failed: Vec<Box<dyn RuleResult>> = vec![Box::new(FailedResult::from("c"))];
+        assert!(!report_of(failed, &[]).has_results());
+    }
+}
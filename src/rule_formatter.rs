@@ -7,7 +7,6 @@ pub enum ResultType {
     Fail,
     Hand,
     Vibe,
-    #[allow(unused)]
     Percentage(usize),
 }
 
@@ -83,9 +82,24 @@ impl std::fmt::Display for RuleFormatter {
     }
 }
 
+#[cfg(test)]
 mod test {
+    use super::*;
+
     #[test]
     fn test_percentage() {
-        todo!();
+        colored::control::set_override(false);
+        let formatter = RuleFormatter {
+            rule_name: "overall",
+            result_type: ResultType::Percentage(72),
+            ..Default::default()
+        };
+        assert_eq!(formatter.to_string(), format!("[  72% ] {:25} ", "overall"));
+
+        let clamped = RuleFormatter {
+            result_type: ResultType::Percentage(150),
+            ..Default::default()
+        };
+        assert!(clamped.to_string().starts_with("[ 100% ]"));
     }
 }
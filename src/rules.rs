@@ -1,8 +1,11 @@
-use crate::{traits::Rule};
+use crate::{options::Options, traits::{Category, Rule}};
 
 pub(crate) mod failed_result;
+mod activity;
+mod authors;
 mod repository_dev_time;
 mod repository_age;
+mod repository_initial_commit;
 mod readme;
 mod repository;
 mod macros;
@@ -13,4 +16,35 @@ pub fn all() -> Vec<&'static dyn Rule> {
     result
 }
 
+pub fn ids() -> Vec<&'static str> {
+    let mut ids: Vec<&'static str> = all().iter().map(|r| r.id()).collect();
+    ids.sort();
+    ids
+}
+
+/// Metadata of a registered rule, with thresholds and weights as `options` would apply them.
+#[derive(Debug, serde::Serialize)]
+pub struct RuleInfo {
+    pub id: &'static str,
+    pub category: Category,
+    pub description: &'static str,
+    pub threshold: f64,
+    pub weight: f64,
+    pub enabled: bool,
+}
 
+pub fn describe(options: &Options) -> Vec<RuleInfo> {
+    let mut infos: Vec<RuleInfo> = all()
+        .into_iter()
+        .map(|rule| RuleInfo {
+            id: rule.id(),
+            category: rule.category(),
+            description: rule.description(),
+            threshold: options.threshold(rule),
+            weight: options.weight(rule),
+            enabled: options.is_enabled(rule),
+        })
+        .collect();
+    infos.sort_by_key(|i| (i.category, i.id));
+    infos
+}
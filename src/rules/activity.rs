@@ -0,0 +1,132 @@
+pub mod commit_hours {
+    use crate::{activity::{CommitHours, BURST_COMMITS, BURST_WINDOW_SECS}, rules::macros::rule_run_impl, traits::Vibe};
+    const RULE_ID: &str = "repository-commit-hours";
+
+    pub struct Rule;
+    pub struct RuleResult {
+        hours: CommitHours,
+        threshold: f64,
+    }
+    impl From<(CommitHours, f64)> for RuleResult {
+        fn from((hours, threshold): (CommitHours, f64)) -> Self {
+            Self { hours, threshold }
+        }
+    }
+    rule_run_impl!(Rule, RULE_ID, crate::activity::get_commit_hours,
+        category: Repository,
+        description: "Spread of each author's commits over their local day (entropy 0..1), VIBE above threshold or on 40+ commits in an hour",
+        threshold: 0.9);
+
+    impl RuleResult {
+        fn burst(&self) -> Option<&crate::activity::Burst> {
+            self.hours.burst.as_ref().filter(|b| b.commits >= BURST_COMMITS)
+        }
+        fn max_entropy(&self) -> &crate::activity::AuthorHours {
+            self.hours
+                .authors
+                .iter()
+                .max_by(|a, b| a.entropy.total_cmp(&b.entropy))
+                .expect("at least one author")
+        }
+    }
+
+    impl crate::traits::RuleResult for RuleResult {
+        fn name(&self) -> &'static str {
+            RULE_ID
+        }
+        fn msg(&self) -> Option<String> {
+            let top = self.max_entropy();
+            let mut msg = format!("Hour entropy: {:.2} ({})", top.entropy, top.author);
+            if let Some(burst) = self.burst() {
+                msg.push_str(&format!(
+                    ", {} commits within {} minutes by {}",
+                    burst.commits,
+                    BURST_WINDOW_SECS / 60,
+                    burst.author
+                ));
+            }
+            Some(msg)
+        }
+        fn is_vibe(&self) -> Vibe {
+            if self.max_entropy().entropy > self.threshold || self.burst().is_some() {
+                Vibe::Yes
+            } else {
+                Vibe::No
+            }
+        }
+        fn vibe_msg(&self) -> String {
+            format!("entropy > {} or {} commits/hour", self.threshold, BURST_COMMITS)
+        }
+        fn value(&self) -> Option<f64> {
+            Some(self.max_entropy().entropy)
+        }
+        fn evidence(&self) -> Vec<String> {
+            let mut evidence: Vec<String> = self.hours.authors.iter().flat_map(|a| a.render()).collect();
+            if let Some(burst) = &self.hours.burst {
+                evidence.push(format!(
+                    "largest burst: {} commits by {} within an hour from {}",
+                    burst.commits,
+                    burst.author,
+                    crate::activity::format_utc(burst.start)
+                ));
+            }
+            evidence
+        }
+    }
+}
+
+pub mod sessions {
+    use crate::{activity::{Sessions, SESSION_GAP_SECS}, rules::macros::rule_run_impl, traits::Vibe};
+    const RULE_ID: &str = "code-sessions";
+
+    pub struct Rule;
+    pub struct RuleResult {
+        sessions: Sessions,
+        threshold: f64,
+    }
+    impl From<(Sessions, f64)> for RuleResult {
+        fn from((sessions, threshold): (Sessions, f64)) -> Self {
+            Self { sessions, threshold }
+        }
+    }
+    rule_run_impl!(Rule, RULE_ID, crate::activity::get_sessions,
+        category: Code,
+        description: "Share of inserted lines written in the largest coding session (commits less than 2 h apart), VIBE above threshold",
+        threshold: 0.75);
+
+    impl crate::traits::RuleResult for RuleResult {
+        fn name(&self) -> &'static str {
+            RULE_ID
+        }
+        fn msg(&self) -> Option<String> {
+            let s = &self.sessions;
+            let count = s.sessions.len();
+            let pluralize = if count == 1 { "" } else { "s" };
+            Some(format!(
+                "{count} session{pluralize}, median {:.1} h, {:.0} lines/session, largest {:.0}% of code in {:.1} h",
+                s.median_hours,
+                s.lines_per_session,
+                s.largest_share * 100.0,
+                s.largest().hours()
+            ))
+        }
+        fn is_vibe(&self) -> Vibe {
+            if self.sessions.largest_share > self.threshold {
+                Vibe::Yes
+            } else {
+                Vibe::No
+            }
+        }
+        fn vibe_msg(&self) -> String {
+            format!("largest > {}%", self.threshold * 100.0)
+        }
+        fn value(&self) -> Option<f64> {
+            Some(self.sessions.largest_share)
+        }
+        fn evidence(&self) -> Vec<String> {
+            let mut evidence = vec![format!("sessions split at pauses over {} h", SESSION_GAP_SECS / 3600)];
+            evidence.extend(self.sessions.render());
+            evidence
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{
+    authors::{AuthorStats, MIN_AUTHOR_COMMITS},
+    error::Failure,
+    options::Options,
+    traits::{Repo, Rule, RuleResult, Vibe},
+};
+
+const RULE_ID: &str = "code-author-signals";
+
+pub struct AuthorSignals;
+
+impl Rule for AuthorSignals {
+    fn id(&self) -> &'static str {
+        RULE_ID
+    }
+    fn category(&self) -> crate::traits::Category {
+        crate::traits::Category::Code
+    }
+    fn description(&self) -> &'static str {
+        "Most per-author commit statistics crossing their history-wide rule's threshold, VIBE above threshold"
+    }
+    fn threshold(&self) -> f64 {
+        1.0
+    }
+    fn run_impl(&self, repo: &Repo) -> Result<Box<dyn RuleResult>, Failure> {
+        let options = &repo.analysis().options;
+        let mut flagged: Vec<Flagged> = crate::authors::get_authors(repo)?
+            .iter()
+            .filter(|a| a.commits >= MIN_AUTHOR_COMMITS)
+            .map(|a| Flagged { stats: a.clone(), signals: signals(a, options) })
+            .collect();
+        if flagged.is_empty() {
+            return Err(Failure::missing(format!("no author with {} or more commits", MIN_AUTHOR_COMMITS)));
+        }
+        flagged.sort_by_key(|f| std::cmp::Reverse(f.signals.len()));
+        Ok(Box::new(AuthorSignalsResult {
+            authors: flagged,
+            threshold: options.threshold(self),
+        }))
+    }
+}
+
+inventory::submit! {
+    crate::traits::RuleReg(&AuthorSignals)
+}
+
+struct Flagged {
+    stats: AuthorStats,
+    /// Rule ids whose threshold the author's own value crosses.
+    signals: Vec<&'static str>,
+}
+
+pub struct AuthorSignalsResult {
+    /// Authors with enough commits, most signals first.
+    authors: Vec<Flagged>,
+    threshold: f64,
+}
+
+impl RuleResult for AuthorSignalsResult {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+    fn msg(&self) -> Option<String> {
+        let top = &self.authors[0];
+        if top.signals.is_empty() {
+            return Some(format!("no signals in {} author(s) with {} or more commits", self.authors.len(), MIN_AUTHOR_COMMITS));
+        }
+        Some(format!(
+            "{}: {} of {} signals ({})",
+            top.stats.label(),
+            top.signals.len(),
+            SIGNALS.len(),
+            top.signals.join(", ")
+        ))
+    }
+    fn is_vibe(&self) -> Vibe {
+        if self.authors[0].signals.len() as f64 > self.threshold { Vibe::Yes } else { Vibe::No }
+    }
+    fn vibe_msg(&self) -> String {
+        format!("> {} signals", self.threshold)
+    }
+    fn value(&self) -> Option<f64> {
+        Some(self.authors[0].signals.len() as f64)
+    }
+    fn evidence(&self) -> Vec<String> {
+        let stats: Vec<AuthorStats> = self.authors.iter().map(|f| f.stats.clone()).collect();
+        let mut evidence = crate::authors::table(&stats);
+        evidence.extend(
+            self.authors
+                .iter()
+                .filter(|f| !f.signals.is_empty())
+                .map(|f| format!("{}: {}", f.stats.label(), f.signals.join(", "))),
+        );
+        evidence
+    }
+}
+
+/// Whether an author's value is on the VIBE side of a rule's threshold.
+type Check = fn(&AuthorStats, f64) -> bool;
+
+/// History-wide rules checked per author.
+const SIGNALS: [(&str, Check); 5] = [
+    ("code-insertions-average", |a, t| a.insertions.is_some_and(|v| v > t)),
+    ("code-deletions-average", |a, t| a.deletions.is_some_and(|v| v > t)),
+    ("code-gap-per-commit", |a, t| a.gap_hours.is_some_and(|v| v < t)),
+    ("code-lines-per-hour", |a, t| a.lines_per_hour.is_some_and(|v| v > t)),
+    ("message-similarity", |a, t| a.msg_similarity.is_some_and(|v| v > t)),
+];
+
+/// Signals raised by `author`, using the thresholds configured for the
+/// history-wide rules.
+fn signals(author: &AuthorStats, options: &Options) -> Vec<&'static str> {
+    let rules = crate::rules::all();
+    SIGNALS
+        .iter()
+        .filter(|(id, check)| {
+            rules
+                .iter()
+                .find(|r| r.id() == *id)
+                .is_some_and(|rule| check(author, options.threshold(*rule)))
+        })
+        .map(|(id, _)| *id)
+        .collect()
+}
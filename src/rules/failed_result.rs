@@ -1,7 +1,8 @@
-    use crate::{rule_formatter::{ResultType, RuleFormatter}, traits::{RuleResult, Vibe}};
+    use crate::{error::Failure, rule_formatter::{ResultType, RuleFormatter}, traits::{RuleResult, Vibe}};
 
     pub struct FailedResult {
         pub(crate) name: &'static str,
+        pub(crate) failure: Failure,
     }
 
     impl RuleResult for FailedResult {
@@ -12,11 +13,15 @@
 				fn is_vibe(&self) -> Vibe {
     				Vibe::Undecided
 				}
+        fn failure(&self) -> Option<String> {
+            Some(self.failure.to_string())
+        }
         fn render(&self) {
             RuleFormatter {
                 rule_name: self.name(),
+                msg: Some(String::from(self.failure.summary())),
+                context_msg: self.failure.to_string(),
                 result_type: ResultType::Fail,
-                ..Default::default()
             }.print();
         }
 
@@ -29,7 +34,8 @@
     impl From<&'static str> for FailedResult {
         fn from(value: &'static str) -> Self {
             Self {
-                name: value
+                name: value,
+                failure: Failure::missing("no value could be computed"),
             }
         }
     }
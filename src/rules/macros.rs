@@ -1,16 +1,43 @@
 macro_rules! rule_run_impl {
-    ($rule:ident, $id:ident, $repo_fn:expr) => {
+    (
+        $rule:ident, $id:ident, $repo_fn:expr,
+        category: $category:ident,
+        description: $description:literal,
+        threshold: $threshold:expr
+        $(, weight: $weight:expr)?
+        $(, range_aware: $range_aware:expr)? $(,)?
+    ) => {
 
             impl crate::traits::Rule for $rule {
+                fn id(&self) -> &'static str {
+                    $id
+                }
+                fn category(&self) -> crate::traits::Category {
+                    crate::traits::Category::$category
+                }
+                fn description(&self) -> &'static str {
+                    $description
+                }
+                fn threshold(&self) -> f64 {
+                    $threshold
+                }
+                $(
+                fn weight(&self) -> f64 {
+                    $weight
+                }
+                )?
+                $(
+                fn range_aware(&self) -> bool {
+                    $range_aware
+                }
+                )?
                 fn run_impl(
                     &self,
                     repo: &crate::traits::Repo,
-                ) -> Result<Box<dyn crate::traits::RuleResult>, crate::traits::RuleID> {
-                    let some_value = $repo_fn(repo);
-                    match some_value {
-                        Some(r) => Ok(Box::new(RuleResult::from(r))),
-                        None => Err($crate::traits::RuleID($id)),
-                    }
+                ) -> Result<Box<dyn crate::traits::RuleResult>, crate::error::Failure> {
+                    let threshold = repo.analysis().options.threshold(self);
+                    let value = $repo_fn(repo)?;
+                    Ok(Box::new(RuleResult::from((value, threshold))))
                 }
             }
             inventory::submit! {
@@ -23,30 +50,41 @@ macro_rules! threshold_rule {
     (
         id: $rule_id:literal,
         module: $module:ident,
+        category: $category:ident,
+        description: $description:literal,
         value_function: $some_fn:expr,
+        $(evidence_function: $evidence_fn:expr,)?
         value_type: $value_ty:ty,
         output_format: $format_str:literal,
         vibe_compare: $cmp_fn:ident,
         vibe_threshold: $cmp_v:expr $(,)?
     ) => {
         pub mod $module {
-            use crate::{
-                traits::{ RuleID }
-            };
             pub struct Rule;
 
             impl crate::traits::Rule for Rule {
+                fn id(&self) -> &'static str {
+                    $rule_id
+                }
+                fn category(&self) -> crate::traits::Category {
+                    crate::traits::Category::$category
+                }
+                fn description(&self) -> &'static str {
+                    $description
+                }
+                fn threshold(&self) -> f64 {
+                    $cmp_v as f64
+                }
                 fn run_impl(
                     &self,
                     repo: &crate::traits::Repo,
-                ) -> Result<Box<dyn crate::traits::RuleResult>, crate::traits::RuleID> {
-                    let some_value = $some_fn(repo);
-                    match some_value {
-                        Some(r) => Ok(Box::new(RuleResult {
-                            value: r,
-                        })),
-                        None => Err(RuleID($rule_id)),
-                    }
+                ) -> Result<Box<dyn crate::traits::RuleResult>, crate::error::Failure> {
+                    let threshold = repo.analysis().options.threshold(self);
+                    Ok(Box::new(RuleResult {
+                        value: $some_fn(repo)?,
+                        threshold,
+                        evidence: $crate::rules::macros::threshold_rule!(@evidence repo $rule_id $($evidence_fn)?),
+                    }))
                 }
             }
             inventory::submit! {
@@ -56,7 +94,9 @@ macro_rules! threshold_rule {
                 traits::{Vibe}
             };
             pub struct RuleResult {
-                pub value: $value_ty
+                pub value: $value_ty,
+                pub threshold: f64,
+                pub evidence: Vec<String>,
             }
             impl crate::traits::RuleResult for RuleResult {
                 fn name(&self) -> &'static str {
@@ -69,20 +109,38 @@ macro_rules! threshold_rule {
                 }
 
                 fn vibe_msg(&self) -> String {
-                    $crate::rules::macros::threshold_rule!(@vibe $cmp_fn $cmp_v)
+                    $crate::rules::macros::threshold_rule!(@vibe $cmp_fn self.threshold)
+                }
+
+                fn value(&self) -> Option<f64> {
+                    Some(self.value as f64)
+                }
+
+                fn evidence(&self) -> Vec<String> {
+                    self.evidence.clone()
                 }
 
                 fn is_vibe(&self) -> Vibe {
-                    if self.value.$cmp_fn(&$cmp_v) { Vibe::Yes } else { Vibe::No }
+                    if (self.value as f64).$cmp_fn(&self.threshold) { Vibe::Yes } else { Vibe::No }
                 }
 
             }
         }
     };
-    (@vibe gt $cmp_v:literal) => {
+    // Only computed when `--explain` asks for it
+    (@evidence $repo:ident $rule_id:literal $evidence_fn:expr) => {
+        match $repo.analysis().options.explains($rule_id) {
+            true => $evidence_fn($repo),
+            false => Vec::new(),
+        }
+    };
+    (@evidence $repo:ident $rule_id:literal) => {
+        Vec::new()
+    };
+    (@vibe gt $cmp_v:expr) => {
         format!("? > {}", $cmp_v)
     };
-    (@vibe lt $cmp_v:literal) => {
+    (@vibe lt $cmp_v:expr) => {
         format!("? < {}", $cmp_v)
     }
 }
@@ -4,9 +4,70 @@ use crate::rules::macros::threshold_rule;
 threshold_rule!(
 id: "message-similarity",
 module: message_similarities,
+category: Messages,
+description: "Similarity of sorted commit summaries (Jaro-Winkler, IQR-weighted), VIBE above threshold",
 value_function: crate::messages::get_message_similarities,
 value_type: f64,
 output_format: "Msg similiarity index: {:.2} ",
 vibe_compare: gt,
 vibe_threshold: 0.75
 );
+
+pub mod ai_signatures {
+    use crate::{messages::SignedCommit, rules::macros::rule_run_impl};
+    const RULE_ID: &str = "message-ai-signatures";
+
+    pub struct Rule;
+    pub struct RuleResult {
+        share: f64,
+        signed: Vec<SignedCommit>,
+        threshold: f64,
+    }
+    impl From<((f64, Vec<SignedCommit>), f64)> for RuleResult {
+        fn from(((share, signed), threshold): ((f64, Vec<SignedCommit>), f64)) -> Self {
+            Self {
+                share,
+                signed,
+                threshold,
+            }
+        }
+    }
+    rule_run_impl!(Rule, RULE_ID, crate::messages::get_ai_signatures,
+        category: Messages,
+        description: "Share of commits with AI tool trailers, signatures or bot identities, VIBE above threshold",
+        threshold: 0.0,
+        weight: 2.0);
+    impl crate::traits::RuleResult for RuleResult {
+        fn name(&self) -> &'static str {
+            RULE_ID
+        }
+        fn msg(&self) -> Option<String> {
+            let count = self.signed.len();
+            if count == 0 {
+                return Some(String::from("no AI tool signatures in commits"));
+            }
+            let pluralize = if count == 1 { "" } else { "s" };
+            let first = &self.signed[0].signatures[0];
+            Some(format!("AI tool signatures in {count} commit{pluralize} ({:.1}% of history), e.g. {first}", self.share * 100.0))
+        }
+        fn is_vibe(&self) -> crate::traits::Vibe {
+            if self.share > self.threshold {
+                crate::traits::Vibe::Yes
+            } else {
+                crate::traits::Vibe::No
+            }
+        }
+        fn vibe_msg(&self) -> String {
+            format!("share > {}%", self.threshold * 100.0)
+        }
+        fn value(&self) -> Option<f64> {
+            Some(self.share)
+        }
+        fn evidence(&self) -> Vec<String> {
+            self.signed
+                .iter()
+                .map(|c| format!("{} {} | {}", &c.id.to_string()[..7], c.summary, c.signatures.join("; ")))
+                .collect()
+        }
+    }
+}
@@ -1,22 +1,33 @@
 use crate::{
     readme::count_emojis,
-    traits::{Rule, RuleID, RuleResult, Vibe},
+    error::Failure,
+    traits::{Rule, RuleResult, Vibe},
 };
 
 const EMOJI_COUNT_RULE: &str = "readme-emoji-count";
 pub struct EmojiCount;
 impl Rule for EmojiCount {
+    fn id(&self) -> &'static str {
+        EMOJI_COUNT_RULE
+    }
+    fn category(&self) -> crate::traits::Category {
+        crate::traits::Category::Readme
+    }
+    fn description(&self) -> &'static str {
+        "Number of emoji in the README, VIBE above threshold"
+    }
+    fn threshold(&self) -> f64 {
+        30.0
+    }
     fn run_impl(
         &self,
         repo: &crate::traits::Repo,
-    ) -> Result<Box<dyn crate::traits::RuleResult>, crate::traits::RuleID> {
-        let readme = crate::readme::get_readme(repo);
-        match readme {
-            Some(r) => Ok(Box::new(EmojiCountResult {
-                count: count_emojis(&r),
-            })),
-            None => Err(RuleID(EMOJI_COUNT_RULE)),
-        }
+    ) -> Result<Box<dyn crate::traits::RuleResult>, Failure> {
+        let readme = crate::readme::get_readme(repo)?;
+        Ok(Box::new(EmojiCountResult {
+            count: count_emojis(&readme.content),
+            threshold: repo.analysis().options.threshold(self),
+        }))
     }
 }
 
@@ -26,48 +37,65 @@ inventory::submit! {
 
 pub struct EmojiCountResult {
     count: usize,
+    threshold: f64,
 }
 impl RuleResult for EmojiCountResult {
     fn name(&self) -> &'static str {
         EMOJI_COUNT_RULE
     }
     fn vibe_msg(&self) -> String {
-        String::from(">30")
+        format!(">{}", self.threshold)
     }
     fn msg(&self) -> Option<String> {
         Some(format!("Emoji count: {}", self.count))
     }
+    fn value(&self) -> Option<f64> {
+        Some(self.count as f64)
+    }
 
     fn is_vibe(&self) -> Vibe {
-        if self.count > 30 { Vibe::Yes } else { Vibe::No }
+        if self.count as f64 > self.threshold { Vibe::Yes } else { Vibe::No }
     }
 }
 
 pub mod readme_llm_words {
     use std::collections::HashMap;
 
-    use crate::rules::macros::rule_run_impl;
+    use crate::{readme::WordMatch, rules::macros::rule_run_impl};
     const RULE_ID: &str = "readme-llm-words";
 
     pub struct Rule;
     pub struct RuleResult {
         count: usize,
         frequent: Vec<(String, usize)>,
+        path: String,
+        matches: Vec<WordMatch>,
+        threshold: f64,
     }
-    impl From<HashMap<String,usize>> for RuleResult {
-        fn from(value: HashMap<String,usize>) -> Self {
+    impl From<((String, Vec<WordMatch>), f64)> for RuleResult {
+        fn from(((path, matches), threshold): ((String, Vec<WordMatch>), f64)) -> Self {
+            let mut value: HashMap<String, usize> = HashMap::new();
+            for m in &matches {
+                *value.entry(m.word.to_string()).or_default() += 1;
+            }
             let count = value.values().sum();
             let mut entries: Vec<(String,usize)> = value.into_iter().collect();
             entries.sort_by_key(|s| s.1);
             let frequent: Vec<(String, usize)> = entries.into_iter().rev().take(3).collect();
             Self {
                 count,
-                frequent
-
+                frequent,
+                path,
+                matches,
+                threshold,
             }
         }
     }
-    rule_run_impl!(Rule,RULE_ID,crate::readme::count_llm_words_repo );
+    rule_run_impl!(Rule,RULE_ID,crate::readme::count_llm_words_repo,
+        category: Readme,
+        description: "Occurrences of typical LLM vocabulary in the README, VIBE above threshold",
+        threshold: 2.0,
+        weight: 1.5);
     impl crate::traits::RuleResult for RuleResult {
         fn msg(&self) -> Option<String> {
             let freq_str = self.frequent.iter().map(|(k,c)| format!("{k} x{c}")).collect::<Vec<_>>().join(", ");
@@ -90,14 +118,117 @@ pub mod readme_llm_words {
         }
 
         fn is_vibe(&self) -> crate::traits::Vibe {
-            if self.count > 2 {
+            if self.count as f64 > self.threshold {
                 crate::traits::Vibe::Yes
             } else {
                 crate::traits::Vibe::No
             }
         }
         fn vibe_msg(&self) -> String {
-            String::from(">2")
+            format!(">{}", self.threshold)
+        }
+        fn value(&self) -> Option<f64> {
+            Some(self.count as f64)
+        }
+        fn evidence(&self) -> Vec<String> {
+            self.matches
+                .iter()
+                .map(|m| match m.form == m.word {
+                    true => format!("{}:{}: {} | {}", self.path, m.line, m.word, m.snippet),
+                    false => format!("{}:{}: {} ({}) | {}", self.path, m.line, m.word, m.form, m.snippet),
+                })
+                .collect()
+        }
+    }
+}
+
+pub mod readme_llm_phrases {
+    use crate::{readme::PhraseMatch, rules::macros::rule_run_impl};
+    const RULE_ID: &str = "readme-llm-phrases";
+
+    pub struct Rule;
+    pub struct RuleResult {
+        score: f64,
+        path: String,
+        matches: Vec<PhraseMatch>,
+        threshold: f64,
+    }
+    impl From<((String, Vec<PhraseMatch>), f64)> for RuleResult {
+        fn from(((path, matches), threshold): ((String, Vec<PhraseMatch>), f64)) -> Self {
+            Self {
+                score: matches.iter().map(|m| m.weight).sum(),
+                path,
+                matches,
+                threshold,
+            }
+        }
+    }
+    rule_run_impl!(Rule, RULE_ID, crate::readme::count_llm_phrases_repo,
+        category: Readme,
+        description: "Weighted score of typical LLM phrases in README prose, VIBE above threshold",
+        threshold: 3.0,
+        weight: 1.5);
+    impl crate::traits::RuleResult for RuleResult {
+        fn name(&self) -> &'static str {
+            RULE_ID
+        }
+        fn msg(&self) -> Option<String> {
+            let count = self.matches.len();
+            if count == 0 {
+                return Some(String::from("No LLM phrases in README"));
+            }
+            let pluralize = if count == 1 { "" } else { "s" };
+            // First three distinct phrases, in README order.
+            let mut phrases: Vec<&str> = Vec::new();
+            for m in &self.matches {
+                if phrases.len() < 3 && !phrases.contains(&m.phrase) {
+                    phrases.push(m.phrase);
+                }
+            }
+            Some(format!("Found {count} LLM phrase{pluralize} in README (score {:.1}): {}", self.score, phrases.join(", ")))
+        }
+        fn is_vibe(&self) -> crate::traits::Vibe {
+            if self.score > self.threshold {
+                crate::traits::Vibe::Yes
+            } else {
+                crate::traits::Vibe::No
+            }
+        }
+        fn vibe_msg(&self) -> String {
+            format!("score > {}", self.threshold)
+        }
+        fn value(&self) -> Option<f64> {
+            Some(self.score)
+        }
+        fn evidence(&self) -> Vec<String> {
+            self.matches
+                .iter()
+                .map(|m| format!("{}:{}:{}: {} (+{}) | {}", self.path, m.line, m.column, m.phrase, m.weight, m.snippet))
+                .collect()
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use crate::traits::RuleResult as _;
+
+        #[test]
+        fn test_msg_lists_distinct_phrases() {
+            let at = |phrase: &'static str, line: usize| PhraseMatch {
+                phrase,
+                weight: 1.0,
+                line,
+                column: 1,
+                offset: 0,
+                snippet: String::new(),
+            };
+            let matches = vec![at("in today's", 1), at("delve into", 2), at("in today's", 3), at("delve into", 4), at("game-changer", 5)];
+            let result = RuleResult::from(((String::from("README.md"), matches), 3.0));
+            assert_eq!(
+                result.msg().unwrap(),
+                "Found 5 LLM phrases in README (score 5.0): in today's, delve into, game-changer"
+            );
         }
     }
 }
@@ -117,9 +248,11 @@ use crate::rules::macros::threshold_rule;
 threshold_rule!(
 id: "readme-headings",
 module: readme_headings,
+category: Readme,
+description: "Number of headings in the README, VIBE above threshold",
 value_function: crate::readme::count_headings_from_repo,
 value_type: usize,
-output_format: "README.md headings count: {}",
+output_format: "README headings count: {}",
 vibe_compare: gt,
 vibe_threshold: 20_usize
 );
@@ -127,9 +260,11 @@ vibe_threshold: 20_usize
 threshold_rule!(
 id: "readme-length",
 module: readme_length,
+category: Readme,
+description: "Number of words in the README, VIBE above threshold",
 value_function: crate::readme::length_in_words,
 value_type: usize,
-output_format: "README.md word count: {}",
+output_format: "README word count: {}",
 vibe_compare: gt,
 vibe_threshold: 1000_usize
 );
@@ -137,7 +272,10 @@ vibe_threshold: 1000_usize
 threshold_rule!(
 id: "code-insertions-average",
 module: code_insertions,
+category: Code,
+description: "Average inserted lines per commit (IQR-filtered), VIBE above threshold",
 value_function: crate::code::average_code_insertions_per_commit,
+evidence_function: crate::code::largest_insertions,
 value_type: f64,
 output_format: "Average insertions (IQR): {:.2}",
 vibe_compare: gt,
@@ -147,7 +285,10 @@ vibe_threshold: 500.0
 threshold_rule!(
 id: "code-deletions-average",
 module: code_deletions,
+category: Code,
+description: "Average deleted lines per commit (IQR-filtered), VIBE above threshold",
 value_function: crate::code::average_code_deletions_per_commit,
+evidence_function: crate::code::largest_deletions,
 value_type: f64,
 output_format: "Average deletions (IQR): {:.2}",
 vibe_compare: gt,
@@ -157,7 +298,10 @@ vibe_threshold: 50.0
 threshold_rule!(
 id: "code-lines-per-hour",
 module: code_lines_per_hour,
+category: Code,
+description: "Average inserted lines per hour between commits, VIBE above threshold",
 value_function: crate::code::average_lines_per_hour,
+evidence_function: crate::code::largest_insertions,
 value_type: f64,
 output_format: "Average lines per hour: {:.2}",
 vibe_compare: gt,
@@ -166,6 +310,8 @@ vibe_threshold: 50.0
 threshold_rule!(
 id: "code-gap-per-commit",
 module: code_gaps_between_commits,
+category: Code,
+description: "Average hours between commits (IQR-filtered), VIBE below threshold",
 value_function: crate::code::average_time_between_commits,
 value_type: f64,
 output_format: "Average time between commits: {:.2} hours",
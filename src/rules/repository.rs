@@ -8,22 +8,28 @@ pub mod llm_files {
     ];
     pub struct Rule;
 
-    rule_run_impl!(Rule, RULE_ID, crate::repo::llm_specific_files);
+    rule_run_impl!(Rule, RULE_ID, crate::repo::llm_specific_files,
+        category: Repository,
+        description: "Files named after LLM tools (CLAUDE.md, .cursorrules, ...) in history, VIBE above threshold",
+        threshold: 0.0,
+        weight: 2.0,
+        range_aware: false);
     pub struct RuleResult {
         pub llm_files: Vec<String>,
+        pub threshold: f64,
     }
-    impl From<Vec<String>> for RuleResult {
-        fn from(value: Vec<String>) -> Self {
-            let mut llm_files = Vec::new();
-            for file in &value {
-            for key in LLM_KEYWORDS {
-                if file.contains(key) {
-                    llm_files.push(file.clone());
-                }
-            }
-            };
+    impl From<(Vec<String>, f64)> for RuleResult {
+        fn from((value, threshold): (Vec<String>, f64)) -> Self {
+            // Once per path, `.cursorrules` matches several keywords
+            let mut llm_files: Vec<String> = value
+                .into_iter()
+                .filter(|file| LLM_KEYWORDS.iter().any(|key| file.contains(key)))
+                .collect();
+            llm_files.sort();
+            llm_files.dedup();
             Self {
-                llm_files
+                llm_files,
+                threshold,
             }
         }
     }
@@ -45,11 +51,23 @@ pub mod llm_files {
             Some(result)
         }
 
+        fn value(&self) -> Option<f64> {
+            Some(self.llm_files.len() as f64)
+        }
+
+        fn evidence(&self) -> Vec<String> {
+            self.llm_files.clone()
+        }
+
+        fn vibe_msg(&self) -> String {
+            format!("files > {}", self.threshold)
+        }
+
         fn is_vibe(&self) -> crate::traits::Vibe {
-            if self.llm_files.is_empty() {
-                crate::traits::Vibe::No
-            } else {
+            if self.llm_files.len() as f64 > self.threshold {
                 crate::traits::Vibe::Yes
+            } else {
+                crate::traits::Vibe::No
             }
         }
     }
@@ -59,19 +77,24 @@ pub mod comment_lines {
     use crate::rules::macros::rule_run_impl;
     const RULE_ID: &str = "code-comment-ratio";
 
-    rule_run_impl!(Rule, RULE_ID, crate::code::count_comment_ratio);
+    rule_run_impl!(Rule, RULE_ID, crate::code::count_comment_ratio,
+        category: Code,
+        description: "Share of source lines containing a comment marker, VIBE above threshold",
+        threshold: 0.075);
     struct Rule;
     struct RuleResult {
         comments: usize,
         lines: usize,
         ratio: f64,
+        threshold: f64,
     }
-    impl From<(usize, usize)> for RuleResult {
-        fn from(value: (usize, usize)) -> Self {
+    impl From<((usize, usize), f64)> for RuleResult {
+        fn from((value, threshold): ((usize, usize), f64)) -> Self {
             Self {
                 comments: value.0,
                 lines: value.1,
                 ratio: value.0 as f64 /value.1 as f64,
+                threshold,
             }
         }
     }
@@ -81,14 +104,17 @@ pub mod comment_lines {
         }
 
         fn is_vibe(&self) -> crate::traits::Vibe {
-            if self.ratio > 0.075 {
+            if self.ratio > self.threshold {
                 crate::traits::Vibe::Yes
             } else {
                 crate::traits::Vibe::No
             }
         }
         fn vibe_msg(&self) -> String {
-            "ratio > 7.5%".into()
+            format!("ratio > {}%", self.threshold * 100.0)
+        }
+        fn value(&self) -> Option<f64> {
+            Some(self.ratio)
         }
 
         fn msg(&self) -> Option<String> {
@@ -1,10 +1,30 @@
-use crate::{traits::{Repo, Rule, RuleID, RuleResult, Vibe}};
+use crate::{error::Failure, traits::{Repo, Rule, RuleResult, Vibe}};
 
 pub struct RepositoryAge;
 
 impl Rule for RepositoryAge {
-    fn run_impl(&self, repo: &Repo) -> Result<Box<dyn RuleResult>, RuleID> {
-        Ok(Box::new(RepositoryAgeResult { age_in_s: secs_since_first_commit(repo) }))
+    fn id(&self) -> &'static str {
+        "repository-age"
+    }
+    fn category(&self) -> crate::traits::Category {
+        crate::traits::Category::Repository
+    }
+    fn description(&self) -> &'static str {
+        "Days since the first commit, VIBE below threshold"
+    }
+    fn threshold(&self) -> f64 {
+        14.0
+    }
+    fn range_aware(&self) -> bool {
+        false
+    }
+    fn run_impl(&self, repo: &Repo) -> Result<Box<dyn RuleResult>, Failure> {
+        let boundary = super::repository_dev_time::get_boundary_ts(repo, true);
+        Ok(Box::new(RepositoryAgeResult {
+            age_in_s: secs_since(boundary.oldest),
+            lower_bound: boundary.truncated,
+            threshold_days: repo.analysis().options.threshold(self),
+        }))
     }
 }
 inventory::submit! {
@@ -12,7 +32,10 @@ inventory::submit! {
 }
 
 pub struct RepositoryAgeResult {
-    age_in_s: u64
+    age_in_s: u64,
+    /// Shallow clone, the repository may be older.
+    lower_bound: bool,
+    threshold_days: f64,
 }
 
 impl RuleResult for  RepositoryAgeResult {
@@ -20,22 +43,30 @@ impl RuleResult for  RepositoryAgeResult {
         "repository-age"
     }
     fn is_vibe(&self) -> Vibe {
-        if self.age_in_s > 24*3600*14 {
+        if self.age_in_s as f64 > 24.0 * 3600.0 * self.threshold_days {
             Vibe::No
+        } else if self.lower_bound {
+            Vibe::Undecided
         } else {
             Vibe::Yes
         }
     }
     fn vibe_msg(&self) -> String {
-            String::from("< 14 days")
+            format!("< {} days", self.threshold_days)
     }
     fn msg(&self) -> Option<String> {
-        Some(format!("Age of repository: {:.2} days",(self.age_in_s as f64 / (24.0*3600.0))))
+        let days = self.age_in_s as f64 / (24.0*3600.0);
+        match self.lower_bound {
+            true => Some(format!("Age of repository: at least {:.2} days (shallow clone)", days)),
+            false => Some(format!("Age of repository: {:.2} days", days)),
+        }
+    }
+    fn value(&self) -> Option<f64> {
+        Some(self.age_in_s as f64 / (24.0*3600.0))
     }
 }
 
-fn secs_since_first_commit(repo: &Repo) -> u64 {
-    let (_, beginning) = super::repository_dev_time::get_boundary_ts(repo);
+fn secs_since(beginning: Option<i64>) -> u64 {
     let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
     let beginning = beginning.unwrap_or(now as i64);
     now - beginning as u64 
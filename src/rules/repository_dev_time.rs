@@ -6,20 +6,41 @@ use crate::traits::Vibe;
 pub enum Age {
     Unknown,
     Days(f64),
+    /// Shallow clone, the real history may be longer.
+    AtLeast(f64),
 }
 pub struct RepositoryDevTime;
 
 impl Rule for RepositoryDevTime {
-    fn run_impl(&self, repo: &Repo) -> Result<Box<dyn RuleResult>, crate::traits::RuleID> {
+    fn id(&self) -> &'static str {
+        "repository-dev-time"
+    }
+    fn category(&self) -> crate::traits::Category {
+        crate::traits::Category::Repository
+    }
+    fn description(&self) -> &'static str {
+        "Days between the first and the last commit, VIBE below threshold"
+    }
+    fn threshold(&self) -> f64 {
+        14.0
+    }
+    fn run_impl(&self, repo: &Repo) -> Result<Box<dyn RuleResult>, crate::error::Failure> {
  
-        let age = match seconds_between_boundary(repo)  {
+        let boundary = get_boundary_ts(repo, false);
+        let age = match seconds_between_boundary(&boundary)  {
             None => Age::Unknown,
+            Some(delta) if boundary.truncated => {
+                Age::AtLeast(delta as f64 / (24.0 * 3600.0) )
+            }
             Some(delta) => {
                 Age::Days(delta as f64 / (24.0 * 3600.0) )
             }
         };
 
-        Ok(Box::new(RepositoryDevTimeResult { age }))
+        Ok(Box::new(RepositoryDevTimeResult {
+            age,
+            threshold_days: repo.analysis().options.threshold(self),
+        }))
     }
 }
 
@@ -29,6 +50,7 @@ inventory::submit! {
 
 pub struct RepositoryDevTimeResult {
     age: Age,
+    threshold_days: f64,
 }
 
 impl RuleResult for RepositoryDevTimeResult {
@@ -39,33 +61,55 @@ impl RuleResult for RepositoryDevTimeResult {
         let msg = match self.age {
             Age::Unknown => String::from("Unknown age"),
             Age::Days(v) => format!("Development over {:4.2} days", v),
+            Age::AtLeast(v) => format!("Development over at least {:4.2} days (shallow clone)", v),
         };
         Some(msg)
     }
     fn vibe_msg(&self) -> String {
-        String::from("< 14 days")
+        format!("< {} days", self.threshold_days)
+    }
+    fn value(&self) -> Option<f64> {
+        match self.age {
+            Age::Unknown => None,
+            Age::Days(v) | Age::AtLeast(v) => Some(v),
+        }
     }
 
 
     fn is_vibe(&self) -> crate::traits::Vibe {
         match self.age {
             Age::Unknown => Vibe::Undecided,
-            Age::Days(v) if v > 14.0 => Vibe::No,
+            Age::Days(v) | Age::AtLeast(v) if v > self.threshold_days => Vibe::No,
             Age::Days(_) => Vibe::Yes,
+            Age::AtLeast(_) => Vibe::Undecided,
         }
 
     }
 }
 
-pub(crate) fn get_boundary_ts(repo: &Repo) -> (Option<i64>, Option<i64>) {
-    let Ok(mut walker) = repo.revwalk() else {
-        return (None, None);
-    };
-    if walker.push_head().is_err() {
-        return (None, None);
+pub(crate) struct Boundary {
+    pub newest: Option<i64>,
+    pub oldest: Option<i64>,
+    /// Commits without parents, empty when the walk stopped before the root.
+    pub roots: Vec<git2::Oid>,
+    /// The walk hit the edge of a shallow clone, so `oldest` is only a lower
+    /// bound of the real history.
+    pub truncated: bool,
+}
+
+/// Timestamps of the newest and the oldest commit.
+///
+/// With `whole_history` the oldest is the root commit even under `--range`,
+/// otherwise it is the oldest commit of the range.
+pub(crate) fn get_boundary_ts(repo: &Repo, whole_history: bool) -> Boundary {
+    let walker = if whole_history { repo.walk_history() } else { repo.walk_from_start() };
+    let mut boundary = Boundary { newest: None, oldest: None, roots: Vec::new(), truncated: false };
+    let Some(mut walker) = walker else {
+        return boundary;
     };
     let mut newest_ts = None;
     let mut oldest_ts = None;
+    let mut last_ts = None;
 
     while let Some(Ok(oid)) = walker.next() {
         let Ok(commit) = repo.find_commit(oid) else {
@@ -75,14 +119,28 @@ pub(crate) fn get_boundary_ts(repo: &Repo) -> (Option<i64>, Option<i64>) {
         if newest_ts.is_none() {
             newest_ts = Some(commit.time().seconds());
         }
+        if repo.is_shallow_root(oid) {
+            boundary.truncated = true;
+        }
         if commit.parent(0).is_err() {
             oldest_ts = Some(commit.time().seconds());
+            // The edge of a shallow clone has no parents either
+            if !repo.is_shallow_root(oid) {
+                boundary.roots.push(oid);
+            }
         }
+        last_ts = Some(commit.time().seconds());
+    }
+    // A range or a shallow clone stops before reaching the root
+    if oldest_ts.is_none() && (boundary.truncated || (!whole_history && repo.is_range())) {
+        oldest_ts = last_ts;
     }
-    (newest_ts, oldest_ts)
+    boundary.newest = newest_ts;
+    boundary.oldest = oldest_ts;
+    boundary
 }
-fn seconds_between_boundary(repo: &Repo) -> Option<usize>{
-        match get_boundary_ts(repo)  {
+fn seconds_between_boundary(boundary: &Boundary) -> Option<usize>{
+        match (boundary.newest, boundary.oldest) {
             (None, _) | (_, None) => None,
             (Some(newest), Some(oldest)) => {
                 Some((newest - oldest) as usize)
@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+
+use git2::{Delta, DiffFindOptions, Oid, TreeWalkMode};
+
+use crate::{
+    error::{AnalysisError, Failure},
+    traits::{Repo, Rule, RuleResult, Vibe},
+};
+
+const RULE_ID: &str = "repository-initial-commit";
+/// Files listed by `--explain`.
+const EVIDENCE_FILES: usize = 5;
+/// Larger blobs (generated files, data) are not counted.
+const MAX_BLOB_BYTES: usize = 1024 * 1024;
+
+/// Line multiset per path of a tree.
+type Lines = HashMap<String, HashMap<String, usize>>;
+
+pub struct RepositoryInitialCommit;
+
+impl Rule for RepositoryInitialCommit {
+    fn id(&self) -> &'static str {
+        RULE_ID
+    }
+    fn category(&self) -> crate::traits::Category {
+        crate::traits::Category::Repository
+    }
+    fn description(&self) -> &'static str {
+        "Share of the current tree's lines already present in the initial commit (following renames), VIBE above threshold"
+    }
+    fn threshold(&self) -> f64 {
+        0.5
+    }
+    fn range_aware(&self) -> bool {
+        false
+    }
+    fn run_impl(&self, repo: &Repo) -> Result<Box<dyn RuleResult>, Failure> {
+        let boundary = super::repository_dev_time::get_boundary_ts(repo, true);
+        if boundary.roots.is_empty() {
+            return Err(match boundary.truncated {
+                true => Failure::missing("initial commit is not in the shallow clone, use --full-history"),
+                false => repo.no_commits(),
+            });
+        }
+        let head = repo.start_tree().ok_or_else(|| repo.no_commits())?;
+        let mut initial = Lines::new();
+        let mut renamed: HashMap<String, String> = HashMap::new();
+        let mut roots = Vec::new();
+        for oid in &boundary.roots {
+            let commit = repo.find_commit(*oid).map_err(|e| AnalysisError::read(oid, e))?;
+            let tree = commit.tree().map_err(|e| AnalysisError::read(oid, e))?;
+            renamed.extend(renames(repo, &tree, &head)?);
+            let lines = tree_lines(repo, &tree)?;
+            roots.push(Root {
+                id: *oid,
+                summary: commit.summary().unwrap_or_default().to_string(),
+                files: lines.len(),
+                lines: lines.values().flat_map(HashMap::values).sum(),
+            });
+            for (path, counts) in lines {
+                let merged = initial.entry(path).or_default();
+                for (line, count) in counts {
+                    *merged.entry(line).or_default() += count;
+                }
+            }
+        }
+        let current = tree_lines(repo, &head)?;
+        let mut surviving: Vec<(String, usize)> = current
+            .iter()
+            .filter_map(|(path, counts)| {
+                let before = initial.get(path).or_else(|| initial.get(renamed.get(path)?))?;
+                Some((path.clone(), shared_lines(before, counts)))
+            })
+            .filter(|(_, shared)| *shared > 0)
+            .collect();
+        surviving.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        let current_lines: usize = current.values().flat_map(HashMap::values).sum();
+        if current_lines == 0 {
+            return Err(Failure::missing(format!("no text files at {}", repo.start_label())));
+        }
+        let share = surviving.iter().map(|(_, shared)| shared).sum::<usize>() as f64 / current_lines as f64;
+        surviving.truncate(EVIDENCE_FILES);
+        Ok(Box::new(RepositoryInitialCommitResult {
+            roots,
+            share,
+            surviving,
+            threshold: repo.analysis().options.threshold(self),
+        }))
+    }
+}
+
+inventory::submit! {
+    crate::traits::RuleReg(&RepositoryInitialCommit)
+}
+
+struct Root {
+    id: Oid,
+    summary: String,
+    files: usize,
+    lines: usize,
+}
+
+pub struct RepositoryInitialCommitResult {
+    roots: Vec<Root>,
+    /// Share of the current tree's lines that were in the initial commit.
+    share: f64,
+    /// Paths with the most lines surviving from the initial commit.
+    surviving: Vec<(String, usize)>,
+    threshold: f64,
+}
+
+impl RuleResult for RepositoryInitialCommitResult {
+    fn name(&self) -> &'static str {
+        RULE_ID
+    }
+    fn msg(&self) -> Option<String> {
+        let files: usize = self.roots.iter().map(|r| r.files).sum();
+        let lines: usize = self.roots.iter().map(|r| r.lines).sum();
+        let roots = match self.roots.len() {
+            1 => String::from("Initial commit"),
+            n => format!("{n} initial commits"),
+        };
+        Some(format!(
+            "{roots}: {files} files, {lines} lines, {:.0}% of current lines",
+            self.share * 100.0
+        ))
+    }
+    fn is_vibe(&self) -> Vibe {
+        if self.share > self.threshold { Vibe::Yes } else { Vibe::No }
+    }
+    fn vibe_msg(&self) -> String {
+        format!("> {}%", self.threshold * 100.0)
+    }
+    fn value(&self) -> Option<f64> {
+        Some(self.share)
+    }
+    fn evidence(&self) -> Vec<String> {
+        let roots = self.roots.iter().map(|r| {
+            let id = r.id.to_string();
+            format!("{} {} files, {} lines: {}", &id[..7], r.files, r.lines, r.summary)
+        });
+        let files = self.surviving.iter().map(|(path, shared)| format!("{path}: {shared} lines since the initial commit"));
+        roots.chain(files).collect()
+    }
+}
+
+/// Current path to initial path of files moved since `root`.
+fn renames(repo: &Repo, root: &git2::Tree, head: &git2::Tree) -> Result<HashMap<String, String>, Failure> {
+    let read = |e| AnalysisError::read("diff against the initial commit", e);
+    let mut diff = repo.diff_tree_to_tree(Some(root), Some(head), None).map_err(read)?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true))).map_err(read)?;
+    Ok(diff
+        .deltas()
+        .filter(|d| d.status() == Delta::Renamed)
+        .filter_map(|d| {
+            let new = d.new_file().path()?.to_string_lossy().into_owned();
+            let old = d.old_file().path()?.to_string_lossy().into_owned();
+            Some((new, old))
+        })
+        .collect())
+}
+
+/// Line multisets of the text files in `tree`, binary and oversized blobs
+/// are skipped. Sizes come from the object header, so oversized blobs are
+/// never loaded.
+fn tree_lines(repo: &Repo, tree: &git2::Tree) -> Result<Lines, Failure> {
+    let odb = repo.odb().map_err(|e| AnalysisError::read("object database", e))?;
+    let mut lines = Lines::new();
+    tree.walk(TreeWalkMode::PreOrder, |root, te| {
+        let _: Option<()> = (|| {
+            if te.kind() != Some(git2::ObjectType::Blob) {
+                return None;
+            }
+            let (size, _) = odb.read_header(te.id()).ok()?;
+            if size > MAX_BLOB_BYTES {
+                return None;
+            }
+            let blob = te.to_object(repo).ok()?.into_blob().ok()?;
+            if blob.is_binary() {
+                return None;
+            }
+            let counts = lines.entry(format!("{}{}", root, te.name()?)).or_default();
+            for line in String::from_utf8_lossy(blob.content()).lines() {
+                *counts.entry(line.to_string()).or_default() += 1;
+            }
+            Some(())
+        })();
+        0
+    })
+    .map_err(|e| AnalysisError::read("tree", e))?;
+    Ok(lines)
+}
+
+/// Size of the intersection of two line multisets, the lines of a file that
+/// survived since `before` without a full blame.
+fn shared_lines(before: &HashMap<String, usize>, after: &HashMap<String, usize>) -> usize {
+    after
+        .iter()
+        .map(|(line, count)| before.get(line).map_or(0, |b| (*b).min(*count)))
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_shared_lines() {
+        let count = |lines: &[&str]| {
+            let mut counts = HashMap::new();
+            for line in lines {
+                *counts.entry(line.to_string()).or_default() += 1;
+            }
+            counts
+        };
+        let before = count(&["fn main() {", "}", "", "}"]);
+        let after = count(&["fn main() {", "    run();", "}", ""]);
+        assert_eq!(shared_lines(&before, &after), 3);
+        assert_eq!(shared_lines(&after, &after), 4);
+        assert_eq!(shared_lines(&HashMap::new(), &after), 0);
+    }
+
+    #[test]
+    fn test_skips_oversized_files_and_follows_renames() {
+        use std::path::Path;
+
+        use crate::{analysis::Analysis, options::Options, repo::Target, staging_dir::StagingDir};
+
+        let root = std::env::temp_dir().join(format!("vibe-coded-test-initial-{}", std::process::id()));
+        let dir = StagingDir::try_new(&root).unwrap();
+        let git = git2::Repository::init(&dir.path).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let commit = |add: &[(&str, String)], remove: &[&str]| {
+            let mut index = git.index().unwrap();
+            for (name, content) in add {
+                std::fs::write(dir.path.join(name), content).unwrap();
+                index.add_path(Path::new(name)).unwrap();
+            }
+            for name in remove {
+                index.remove_path(Path::new(name)).unwrap();
+            }
+            index.write().unwrap();
+            let tree = git.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = git.head().ok().and_then(|h| h.peel_to_commit().ok());
+            git.commit(Some("HEAD"), &sig, &sig, "change", &tree, parent.iter().collect::<Vec<_>>().as_slice())
+                .unwrap();
+        };
+        let lib = String::from("fn a() {}\nfn b() {}\nfn c() {}\nfn d() {}\n");
+        commit(&[("huge.txt", "data\n".repeat(MAX_BLOB_BYTES / 4)), ("lib.rs", lib.clone())], &[]);
+        commit(&[("main.rs", String::from("fn e() {}\nfn f() {}\nfn g() {}\nfn h() {}\n")), ("src.rs", lib)], &["lib.rs"]);
+
+        let analysis = Analysis::new(&Target::Local(dir.path.clone()), &Options::default()).unwrap();
+        let repo = analysis.open().unwrap();
+        let result = RepositoryInitialCommit.run_impl(&repo).ok().unwrap();
+        assert_eq!(result.msg().unwrap(), "Initial commit: 1 files, 4 lines, 50% of current lines");
+        assert!(result.evidence().contains(&String::from("src.rs: 4 lines since the initial commit")));
+    }
+}
@@ -1,32 +1,89 @@
-use crate::repo::clone_repository;
+use std::sync::Arc;
+
+use crate::analysis::Analysis;
+use crate::error::Failure;
 use crate::rules::failed_result::FailedResult;
 
+use crate::report::RuleReport;
 use crate::rule_formatter::RuleFormatter;
 
-pub type Repo = git2::Repository;
+pub type Repo = crate::analysis::Repo;
 
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, serde::Serialize)]
 pub enum Vibe {
+    #[serde(rename = "vibe")]
     Yes,
+    #[serde(rename = "hand")]
     No,
+    #[serde(rename = "undecided")]
     Undecided,
 }
 
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    Readme,
+    Code,
+    Repository,
+    Messages,
+}
+
+impl std::fmt::Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(match self {
+            Category::Readme => "readme",
+            Category::Code => "code",
+            Category::Repository => "repository",
+            Category::Messages => "messages",
+        })
+    }
+}
+
 pub struct RuleReg(pub &'static dyn Rule);
 inventory::collect!(RuleReg);
 
-pub struct RuleID(pub &'static str);
-
 pub trait Rule: Send + Sync {
-    fn run_impl(&self, repo: &Repo) -> Result<Box<dyn RuleResult>, RuleID>;
+    fn id(&self) -> &'static str;
+    fn category(&self) -> Category;
+    /// One-line summary of what the rule measures and when it says VIBE.
+    fn description(&self) -> &'static str;
+    /// Default value the rule compares against, overridable per run.
+    fn threshold(&self) -> f64;
+    /// Default contribution of the rule's verdict to the overall score.
+    fn weight(&self) -> f64 {
+        1.0
+    }
+    /// Whether `--range` restricts what the rule looks at, or it still
+    /// reflects the whole tree/history.
+    fn range_aware(&self) -> bool {
+        true
+    }
+    fn run_impl(&self, repo: &Repo) -> Result<Box<dyn RuleResult>, Failure>;
+    /// Runs only this rule on `url`, or on a local path in place.
+    #[deprecated(note = "use `vibe_coded::run_rules` with `Options::only`")]
     fn run_url(&self, url: &str) -> Box<dyn RuleResult> {
-        let repo = clone_repository(url).unwrap();
-        self.run(&repo)
+        let target = crate::repo::Target::parse(url);
+        match Analysis::new(&target, &crate::options::Options::default()) {
+            Ok(analysis) => self.run_shared(&analysis),
+            Err(e) => Box::new(FailedResult { name: self.id(), failure: e.into() }),
+        }
+    }
+    fn run_shared(&self, analysis: &Arc<Analysis>) -> Box<dyn RuleResult> {
+        match analysis.open() {
+            Ok(repo) => self.run(&repo),
+            Err(e) => Box::new(FailedResult {
+                name: self.id(),
+                failure: e.into(),
+            }),
+        }
     }
    fn run(&self, repo: &Repo) -> Box<dyn RuleResult> {
         match self.run_impl(repo) {
             Ok(r) => r,
-            Err(e) => Box::new(FailedResult::from(e.0)),
+            Err(failure) => Box::new(FailedResult {
+                name: self.id(),
+                failure,
+            }),
         }
     }
 
@@ -50,6 +107,28 @@ pub trait RuleResult: Send + Sync {
     fn msg(&self) -> Option<String> {
         None
     }
+    /// Raw numeric value the verdict was derived from, if the rule has one.
+    fn value(&self) -> Option<f64> {
+        None
+    }
+    /// What triggered the verdict (matched lines, files, commits), shown with `--explain`.
+    fn evidence(&self) -> Vec<String> {
+        Vec::new()
+    }
+    /// Reason the rule could not produce a verdict, `None` for regular results.
+    fn failure(&self) -> Option<String> {
+        None
+    }
+    fn report(&self) -> RuleReport {
+        RuleReport {
+            id: self.name(),
+            verdict: self.is_vibe(),
+            value: self.value(),
+            threshold: self.vibe_msg(),
+            message: self.msg(),
+            evidence: Vec::new(),
+        }
+    }
 
 }
 